@@ -0,0 +1,274 @@
+use std::fmt::Write;
+
+use hsml::{
+    compiler::error::HsmlCompileError,
+    formatter::error::HsmlFormatError,
+    linter::{Diagnostic, Severity},
+    parser::{error::HsmlParseError, span::Position},
+};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const CYAN: &str = "\x1b[1;36m";
+
+const TAB_WIDTH: usize = 4;
+
+/// A message pointing at a location of a source file, rendered like rustc does.
+///
+/// ```text
+/// error: duplicate id `id2`, a tag can only have one id
+///  --> index.hsml:1:8
+///   |
+/// 1 | div#id1#id2
+///   |        ^
+///   |
+///   = hint: remove one of the ids
+/// ```
+pub struct CodeFrame<'a> {
+    pub severity: Severity,
+    /// Rendered in brackets behind the severity, e.g. the name of a lint rule
+    pub code: Option<&'a str>,
+    pub message: String,
+    pub file_name: &'a str,
+    pub source: &'a str,
+    pub start: Position,
+    /// Underlines up to this position if it is on the same line as `start`, otherwise only one caret is rendered
+    pub end: Option<Position>,
+    pub hint: Option<String>,
+}
+
+impl<'a> CodeFrame<'a> {
+    pub fn from_parse_error(error: &HsmlParseError, file_name: &'a str, source: &'a str) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: None,
+            message: error.message(),
+            file_name,
+            source,
+            start: error.position(),
+            end: None,
+            hint: error.hint().map(String::from),
+        }
+    }
+
+    pub fn from_compile_error(
+        error: &HsmlCompileError,
+        file_name: &'a str,
+        source: &'a str,
+    ) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: None,
+            message: error.message(),
+            file_name,
+            source,
+            start: error.position(),
+            end: None,
+            hint: error.hint().map(String::from),
+        }
+    }
+
+    pub fn from_format_error(error: &HsmlFormatError, file_name: &'a str, source: &'a str) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: None,
+            message: error.message(),
+            file_name,
+            source,
+            start: error.position(),
+            end: None,
+            hint: None,
+        }
+    }
+
+    pub fn from_diagnostic(diagnostic: &Diagnostic, file_name: &'a str, source: &'a str) -> Self {
+        Self {
+            severity: diagnostic.severity,
+            code: Some(diagnostic.rule),
+            message: diagnostic.message.clone(),
+            file_name,
+            source,
+            start: diagnostic.span.start,
+            end: Some(diagnostic.span.end),
+            hint: None,
+        }
+    }
+
+    pub fn render(&self, color: bool) -> String {
+        let paint = |style: &'static str| if color { style } else { "" };
+        let reset = paint(RESET);
+
+        let line_number = self.start.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        let line = self
+            .source
+            .lines()
+            .nth(self.start.line.saturating_sub(1))
+            .unwrap_or("");
+
+        let underline_offset = display_width(line, self.start.column.saturating_sub(1));
+        let underline_len = match self.end {
+            Some(end) if end.line == self.start.line && end.column > self.start.column => {
+                display_width(line, end.column - 1) - underline_offset
+            }
+            _ => 1,
+        };
+
+        let severity_color = match self.severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+            Severity::Info => CYAN,
+        };
+
+        let code = self
+            .code
+            .map(|code| format!("[{code}]"))
+            .unwrap_or_default();
+
+        let mut frame = String::new();
+
+        let _ = writeln!(
+            frame,
+            "{}{}{code}{reset}{}: {}{reset}",
+            paint(severity_color),
+            self.severity.as_str(),
+            paint(BOLD),
+            self.message
+        );
+        let _ = writeln!(
+            frame,
+            "{gutter}{}-->{reset} {}:{}:{}",
+            paint(BLUE),
+            self.file_name,
+            self.start.line,
+            self.start.column
+        );
+        let _ = writeln!(frame, "{gutter} {}|{reset}", paint(BLUE));
+        let _ = writeln!(
+            frame,
+            "{}{line_number} |{reset} {}",
+            paint(BLUE),
+            expand_tabs(line)
+        );
+        let _ = write!(
+            frame,
+            "{gutter} {}|{reset} {}{}{}{reset}",
+            paint(BLUE),
+            " ".repeat(underline_offset),
+            paint(severity_color),
+            "^".repeat(underline_len)
+        );
+
+        if let Some(hint) = &self.hint {
+            let _ = write!(
+                frame,
+                "\n{gutter} {}|{reset}\n{gutter} {}={reset} {}hint{reset}: {hint}",
+                paint(BLUE),
+                paint(BLUE),
+                paint(BOLD),
+            );
+        }
+
+        frame
+    }
+}
+
+fn expand_tabs(line: &str) -> String {
+    line.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+/// Width of the first `chars` characters of `line` once tabs got expanded.
+fn display_width(line: &str, chars: usize) -> usize {
+    line.chars()
+        .take(chars)
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum::<usize>()
+        // positions at the end of the line point right behind the last character
+        + chars.saturating_sub(line.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use hsml::{
+        linter::{Severity, lint, rules::builtin_rules},
+        parser::{parse::parse, span::Position},
+    };
+
+    use super::CodeFrame;
+
+    #[test]
+    fn it_should_render_parse_error() {
+        let source = "div\n\tp\n\t  span\n";
+        let error = parse(source).unwrap_err();
+
+        let frame = CodeFrame::from_parse_error(&error, "index.hsml", source).render(false);
+
+        assert_eq!(
+            frame,
+            "error: mixed indentation
+ --> index.hsml:3:1
+  |
+3 |       span
+  | ^
+  |
+  = hint: mixed tabs and spaces in indentation"
+        );
+    }
+
+    #[test]
+    fn it_should_underline_range() {
+        let source = "div\n\tp(class=\"a\" class=\"b\")\n";
+
+        let frame = CodeFrame {
+            severity: Severity::Error,
+            code: None,
+            message: String::from("duplicate attribute `class`"),
+            file_name: "index.hsml",
+            source,
+            start: Position::new(17, 2, 14),
+            end: Some(Position::new(26, 2, 23)),
+            hint: None,
+        }
+        .render(false);
+
+        assert_eq!(
+            frame,
+            "error: duplicate attribute `class`
+ --> index.hsml:2:14
+  |
+2 |     p(class=\"a\" class=\"b\")
+  |                 ^^^^^^^^^"
+        );
+    }
+
+    #[test]
+    fn it_should_render_colors() {
+        let source = "div#a#b\n";
+        let error = parse(source).unwrap_err();
+
+        let frame = CodeFrame::from_parse_error(&error, "index.hsml", source).render(true);
+
+        assert!(frame.starts_with("\x1b[1;31merror\x1b[0m"));
+    }
+
+    #[test]
+    fn it_should_render_diagnostic() {
+        let source = "div.a.a\n";
+        let diagnostics = lint(&parse(source).unwrap(), &builtin_rules());
+
+        let frame = CodeFrame::from_diagnostic(&diagnostics[0], "index.hsml", source).render(false);
+
+        assert_eq!(
+            frame,
+            "warning[duplicate-class]: duplicate class `a`
+ --> index.hsml:1:6
+  |
+1 | div.a.a
+  |      ^^"
+        );
+    }
+}
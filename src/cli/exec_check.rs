@@ -1,5 +1,111 @@
+use std::{env, fs, path::PathBuf};
+
 use clap::ArgMatches;
+use hsml::{
+    linter::{Diagnostic, Severity, lint, rules::builtin_rules},
+    parser::{error::HsmlParseError, parse::parse, span::Span},
+};
+use serde::Serialize;
+
+use super::{code_frame::CodeFrame, collect_hsml_files, read_source, use_color};
+
+#[derive(Serialize)]
+struct FileDiagnostic {
+    file: String,
+    #[serde(flatten)]
+    diagnostic: Diagnostic,
+}
+
+fn parse_error_diagnostic(error: &HsmlParseError) -> Diagnostic {
+    Diagnostic {
+        rule: "parse-error",
+        severity: Severity::Error,
+        message: error.message(),
+        span: Span::new(error.position(), error.position()),
+    }
+}
+
+pub fn exec_check(matches: &ArgMatches) -> Result<(), String> {
+    let path = matches.get_one::<PathBuf>("path");
+    let json = matches
+        .get_one::<String>("format")
+        .is_some_and(|format| format == "json");
+    let color = use_color(matches);
+
+    let sources = if path.is_some_and(|path| path.as_os_str() == "-") {
+        vec![read_source(None)?]
+    } else {
+        let fallback_path = env::current_dir().expect("Unable to get current directory");
+        let path = path.unwrap_or(&fallback_path);
+
+        collect_hsml_files(path)?
+            .into_iter()
+            .map(|file| {
+                fs::read_to_string(&file)
+                    .map(|content| (file.display().to_string(), content))
+                    .map_err(|err| format!("Unable to read {}: {}", file.display(), err))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut file_diagnostics = Vec::new();
+
+    for (file_name, content) in &sources {
+        let diagnostics = match parse(content) {
+            Ok(hsml_ast) => lint(&hsml_ast, &builtin_rules()),
+            Err(err) => {
+                if !json {
+                    let frame = CodeFrame::from_parse_error(&err, file_name, content);
+                    eprintln!("{}\n", frame.render(color));
+                }
+
+                file_diagnostics.push(FileDiagnostic {
+                    file: file_name.clone(),
+                    diagnostic: parse_error_diagnostic(&err),
+                });
+
+                continue;
+            }
+        };
+
+        for diagnostic in diagnostics {
+            if !json {
+                let frame = CodeFrame::from_diagnostic(&diagnostic, file_name, content);
+                eprintln!("{}\n", frame.render(color));
+            }
+
+            file_diagnostics.push(FileDiagnostic {
+                file: file_name.clone(),
+                diagnostic,
+            });
+        }
+    }
+
+    if json {
+        let json =
+            serde_json::to_string_pretty(&file_diagnostics).map_err(|err| err.to_string())?;
+        println!("{}", json);
+    }
+
+    let count = |severity: Severity| {
+        file_diagnostics
+            .iter()
+            .filter(|file_diagnostic| file_diagnostic.diagnostic.severity == severity)
+            .count()
+    };
+    let errors = count(Severity::Error);
+    let warnings = count(Severity::Warning);
+
+    if errors > 0 {
+        return Err(format!(
+            "Found {} error(s) and {} warning(s)",
+            errors, warnings
+        ));
+    }
+
+    if !json && warnings > 0 {
+        eprintln!("Found {} warning(s)", warnings);
+    }
 
-pub fn exec_check(_matches: &ArgMatches) -> Result<(), &str> {
-    todo!("Check")
+    Ok(())
 }
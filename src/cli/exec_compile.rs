@@ -2,36 +2,63 @@ use std::{env, fs, path::PathBuf};
 
 use clap::ArgMatches;
 use hsml::{
-    compiler::{HsmlCompileOptions, compile},
+    compiler::{HsmlCompileOptions, HsmlOutputMode, HsmlPrettyOptions, compile},
     parser::parse::parse,
 };
 
-pub fn exec_compile(matches: &ArgMatches) -> Result<(), &str> {
+use super::{code_frame::CodeFrame, use_color};
+
+/// Compile options from the arguments of the `compile` subcommand.
+fn compile_options(matches: &ArgMatches) -> HsmlCompileOptions {
+    let mode = matches.get_one::<String>("mode").map(String::as_str);
+
+    HsmlCompileOptions {
+        mode: match mode {
+            Some("xhtml") => HsmlOutputMode::Xhtml,
+            Some("xml") => HsmlOutputMode::Xml,
+            _ => HsmlOutputMode::Html5,
+        },
+        // an explicit `--mode` takes precedence over the doctype
+        mode_from_doctype: mode.is_none(),
+        pretty: matches.get_flag("pretty").then(HsmlPrettyOptions::default),
+        ..Default::default()
+    }
+}
+
+pub fn exec_compile(matches: &ArgMatches) -> Result<(), String> {
     println!("Compiling...");
     let path = matches.get_one::<PathBuf>("path");
     let out = matches.get_one::<PathBuf>("output");
+    let color = use_color(matches);
+
+    let options = compile_options(matches);
 
     let fallback_path = env::current_dir().expect("Unable to get current directory");
     let path = path.unwrap_or(&fallback_path);
 
     if path.is_dir() {
-        compile_hsml_files_in_dir(path)
+        compile_hsml_files_in_dir(path, &options, color)
     } else if path.is_file() {
-        compile_file(path, out)
+        compile_file(path, out, &options, color)
     } else {
-        Err("Path must be a file or directory")
+        Err(String::from("Path must be a file or directory"))
     }
 }
 
-fn compile_file(file: &PathBuf, out_file: Option<&PathBuf>) -> Result<(), &'static str> {
+fn compile_file(
+    file: &PathBuf,
+    out_file: Option<&PathBuf>,
+    options: &HsmlCompileOptions,
+    color: bool,
+) -> Result<(), String> {
     // check that file exists
     if !file.exists() {
-        return Err("File does not exist");
+        return Err(String::from("File does not exist"));
     }
 
     // check that file is a file
     if !file.is_file() {
-        return Err("Given file must be a file");
+        return Err(String::from("Given file must be a file"));
     }
 
     // check that file ends with .hsml
@@ -48,14 +75,14 @@ fn compile_file(file: &PathBuf, out_file: Option<&PathBuf>) -> Result<(), &'stat
     let out_file = out_file.unwrap_or(&fallback_out_file);
 
     // parse the file
-    let hsml_ast = if let Ok((_, hsml_ast)) = parse(&content) {
-        hsml_ast
-    } else {
-        return Err("Unable to parse file");
-    };
+    let hsml_ast = parse(&content).map_err(|err| {
+        CodeFrame::from_parse_error(&err, &file.display().to_string(), &content).render(color)
+    })?;
 
     // compile the AST
-    let html_content = compile(&hsml_ast, &HsmlCompileOptions::default());
+    let html_content = compile(&hsml_ast, options).map_err(|err| {
+        CodeFrame::from_compile_error(&err, &file.display().to_string(), &content).render(color)
+    })?;
 
     fs::write(out_file, html_content).expect("Unable to write file");
 
@@ -67,19 +94,56 @@ fn compile_file(file: &PathBuf, out_file: Option<&PathBuf>) -> Result<(), &'stat
     Ok(())
 }
 
-fn compile_hsml_files_in_dir(dir: &PathBuf) -> Result<(), &'static str> {
+fn compile_hsml_files_in_dir(
+    dir: &PathBuf,
+    options: &HsmlCompileOptions,
+    color: bool,
+) -> Result<(), String> {
     // compile all hsml files in the directory and call this function recursively on all subdirectories
-    // if there is an error, ignore it and continue
+    // if there is an error, report it and continue
     for entry in fs::read_dir(dir).expect("Unable to read directory") {
         let entry = entry.expect("Unable to read directory entry");
         let path = entry.path();
 
         if path.is_dir() {
-            compile_hsml_files_in_dir(&path).ok();
-        } else if path.is_file() {
-            compile_file(&path, None).ok();
+            compile_hsml_files_in_dir(&path, options, color).ok();
+        } else if path.is_file()
+            && path.extension().is_some_and(|ext| ext == "hsml")
+            && let Err(err) = compile_file(&path, None, options, color)
+        {
+            eprintln!("{}", err);
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use hsml::compiler::HsmlOutputMode;
+
+    use super::compile_options;
+    use crate::cli::cli;
+
+    fn compile_matches(args: &[&str]) -> clap::ArgMatches {
+        let matches = cli().get_matches_from([&["hsml", "compile"], args].concat());
+
+        matches.subcommand_matches("compile").unwrap().clone()
+    }
+
+    #[test]
+    fn it_should_use_mode_of_doctype_without_explicit_mode() {
+        let options = compile_options(&compile_matches(&[]));
+
+        assert_eq!(options.mode, HsmlOutputMode::Html5);
+        assert!(options.mode_from_doctype);
+    }
+
+    #[test]
+    fn it_should_prefer_explicit_mode_over_doctype() {
+        let options = compile_options(&compile_matches(&["--mode", "xhtml"]));
+
+        assert_eq!(options.mode, HsmlOutputMode::Xhtml);
+        assert!(!options.mode_from_doctype);
+    }
+}
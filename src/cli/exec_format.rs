@@ -1,5 +1,94 @@
+use std::{env, fs, path::PathBuf};
+
 use clap::ArgMatches;
+use hsml::{
+    formatter::{HsmlFormatOptions, format},
+    parser::parse::parse,
+};
+
+use super::{code_frame::CodeFrame, collect_hsml_files, read_source, use_color};
+
+pub fn exec_format(matches: &ArgMatches) -> Result<(), String> {
+    let path = matches.get_one::<PathBuf>("path");
+    let check = matches.get_flag("check");
+    let color = use_color(matches);
+
+    if path.is_some_and(|path| path.as_os_str() == "-") {
+        let (file_name, content) = read_source(None)?;
+        let formatted = format_content(&file_name, &content, color)?;
+
+        if check {
+            return if formatted == content {
+                Ok(())
+            } else {
+                Err(String::from("stdin is not formatted"))
+            };
+        }
+
+        print!("{}", formatted);
+
+        return Ok(());
+    }
+
+    let fallback_path = env::current_dir().expect("Unable to get current directory");
+    let path = path.unwrap_or(&fallback_path);
+
+    let mut unformatted_count = 0;
+    let mut failed = false;
+
+    for file in collect_hsml_files(path)? {
+        // report the error and continue with the next file
+        match format_file(&file, check, color) {
+            Ok(true) if check => {
+                println!("Would reformat {}", file.display());
+                unformatted_count += 1;
+            }
+            Ok(true) => println!("Formatted {}", file.display()),
+            Ok(false) => {}
+            Err(err) => {
+                eprintln!("{}", err);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        Err(String::from("Some files could not be formatted"))
+    } else if unformatted_count > 0 {
+        Err(format!(
+            "{} file(s) would be reformatted",
+            unformatted_count
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn format_content(file_name: &str, content: &str, color: bool) -> Result<String, String> {
+    let hsml_ast = parse(content)
+        .map_err(|err| CodeFrame::from_parse_error(&err, file_name, content).render(color))?;
+
+    format(&hsml_ast, &HsmlFormatOptions::default())
+        .map_err(|err| CodeFrame::from_format_error(&err, file_name, content).render(color))
+}
+
+/// Format given file, only writes it if `check` is not set.
+///
+/// Returns whether the formatted content differs from the file.
+fn format_file(file: &PathBuf, check: bool, color: bool) -> Result<bool, String> {
+    let content = fs::read_to_string(file)
+        .map_err(|err| format!("Unable to read {}: {}", file.display(), err))?;
+
+    let formatted = format_content(&file.display().to_string(), &content, color)?;
+
+    if formatted == content {
+        return Ok(false);
+    }
+
+    if !check {
+        fs::write(file, formatted)
+            .map_err(|err| format!("Unable to write {}: {}", file.display(), err))?;
+    }
 
-pub fn exec_format(_matches: &ArgMatches) -> Result<(), &str> {
-    todo!("Format");
+    Ok(true)
 }
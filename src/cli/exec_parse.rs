@@ -1,5 +1,23 @@
+use std::path::PathBuf;
+
 use clap::ArgMatches;
+use hsml::parser::{json::to_json, parse::parse};
+
+use super::{code_frame::CodeFrame, read_source, use_color};
+
+pub fn exec_parse(matches: &ArgMatches) -> Result<(), String> {
+    let path = matches.get_one::<PathBuf>("path");
+    let pretty = !matches.get_flag("compact");
+
+    let (file_name, content) = read_source(path)?;
+
+    let hsml_ast = parse(&content).map_err(|err| {
+        CodeFrame::from_parse_error(&err, &file_name, &content).render(use_color(matches))
+    })?;
+
+    let json = to_json(&hsml_ast, pretty).map_err(|err| err.to_string())?;
+
+    println!("{}", json);
 
-pub fn exec_parse(_matches: &ArgMatches) -> Result<(), &str> {
-    todo!("Parse");
+    Ok(())
 }
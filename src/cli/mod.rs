@@ -1,7 +1,12 @@
-use std::path::PathBuf;
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Read},
+    path::{Path, PathBuf},
+};
 
-use clap::{Command, arg, command, value_parser};
+use clap::{ArgMatches, ColorChoice, Command, arg, command, value_parser};
 
+pub mod code_frame;
 pub mod exec_check;
 pub mod exec_compile;
 pub mod exec_format;
@@ -11,6 +16,12 @@ pub fn cli() -> Command {
     command!()
         .about("HSML command line tool")
         .subcommand_required(true)
+        .arg(
+            arg!(--color <WHEN> "Whether to use colors when printing errors")
+                .value_parser(value_parser!(ColorChoice))
+                .default_value("auto")
+                .global(true),
+        )
         .subcommand(
             Command::new("compile")
                 .about("Compiles given .hsml file or directory to .html")
@@ -21,12 +32,107 @@ pub fn cli() -> Command {
                 .arg(
                     arg!(output: -o --out <OUTPUT> "Output file or directory")
                         .value_parser(value_parser!(PathBuf)),
-                ),
+                )
+                .arg(
+                    arg!(--mode <MODE> "Dialect of the generated markup [default: dialect of the doctype or html5]")
+                        .value_parser(["html5", "xhtml", "xml"]),
+                )
+                .arg(arg!(--pretty "Put block elements on their own indented lines")),
         )
         .subcommand(
             Command::new("parse")
-                .about("Parse given .hsml file and print the AST to stdout as JSON"),
+                .about("Parse given .hsml file and print the AST to stdout as JSON")
+                .arg(
+                    arg!(path: [PATH] "Path to .hsml file, reads from stdin if omitted or `-`")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(arg!(--pretty "Print indented JSON (default)").conflicts_with("compact"))
+                .arg(arg!(--compact "Print JSON on a single line")),
+        )
+        .subcommand(
+            Command::new("fmt")
+                .about("Format given .hsml file or directory in place")
+                .arg(
+                    arg!(path: [PATH] "Path to .hsml file or directory containing .hsml files, formats stdin to stdout if `-`")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(arg!(--check "Only check whether the files are formatted, exits non-zero otherwise")),
         )
-        .subcommand(Command::new("fmt").about("Format given .hsml file or directory"))
-        .subcommand(Command::new("check").about("Check given .hsml file or directory"))
+        .subcommand(
+            Command::new("check")
+                .about("Check given .hsml file or directory for problems, exits non-zero on errors")
+                .arg(
+                    arg!(path: [PATH] "Path to .hsml file or directory containing .hsml files, checks stdin if `-`")
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(--format <FORMAT> "Output format of the diagnostics")
+                        .value_parser(["human", "json"])
+                        .default_value("human"),
+                ),
+        )
+}
+
+/// Resolve the `--color` argument, `auto` only uses colors if stderr is a terminal and `NO_COLOR` is not set.
+pub fn use_color(matches: &ArgMatches) -> bool {
+    match matches.get_one::<ColorChoice>("color") {
+        Some(ColorChoice::Always) => true,
+        Some(ColorChoice::Never) => false,
+        Some(ColorChoice::Auto) | None => {
+            io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none()
+        }
+    }
+}
+
+/// Read the source of given file or from stdin if there is no path or it is `-`.
+///
+/// Returns the name to use for the source in messages together with its content.
+pub fn read_source(path: Option<&PathBuf>) -> Result<(String, String), String> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => {
+            let content = fs::read_to_string(path)
+                .map_err(|err| format!("Unable to read {}: {}", path.display(), err))?;
+
+            Ok((path.display().to_string(), content))
+        }
+        _ => {
+            let mut content = String::new();
+            io::stdin()
+                .read_to_string(&mut content)
+                .map_err(|err| format!("Unable to read stdin: {}", err))?;
+
+            Ok((String::from("<stdin>"), content))
+        }
+    }
+}
+
+/// Collect given .hsml file or all .hsml files inside of given directory and its subdirectories.
+pub fn collect_hsml_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    if !path.is_dir() {
+        return Err(String::from("Path must be a file or directory"));
+    }
+
+    let mut files = Vec::new();
+
+    let entries = fs::read_dir(path)
+        .map_err(|err| format!("Unable to read directory {}: {}", path.display(), err))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("Unable to read directory entry: {}", err))?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            files.extend(collect_hsml_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "hsml") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
 }
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use crate::parser::span::Position;
+
+/// An error that occurred while compiling a parsed hsml source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HsmlCompileError {
+    /// Void elements like `img` can not have children or text
+    VoidElementContent { tag: String, position: Position },
+    /// Nodes like attributes can only be compiled as part of a tag
+    UnsupportedNode { position: Position },
+}
+
+impl HsmlCompileError {
+    pub fn position(&self) -> Position {
+        match self {
+            HsmlCompileError::VoidElementContent { position, .. }
+            | HsmlCompileError::UnsupportedNode { position } => *position,
+        }
+    }
+
+    /// A stable identifier of the kind of error, e.g. for tooling.
+    pub fn code(&self) -> &'static str {
+        match self {
+            HsmlCompileError::VoidElementContent { .. } => "void-element-content",
+            HsmlCompileError::UnsupportedNode { .. } => "unsupported-node",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            HsmlCompileError::VoidElementContent { tag, .. } => {
+                format!("void element `{tag}` can not have content")
+            }
+            HsmlCompileError::UnsupportedNode { .. } => {
+                String::from("node can not be compiled at this position")
+            }
+        }
+    }
+
+    /// A short suggestion on how to fix the error, if there is one.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            HsmlCompileError::VoidElementContent { .. } => {
+                Some("remove the text and child nodes or use an element that is not void")
+            }
+            HsmlCompileError::UnsupportedNode { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for HsmlCompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = self.position();
+
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message(),
+            position.line,
+            position.column
+        )
+    }
+}
+
+impl std::error::Error for HsmlCompileError {}
@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+fn escape(input: &str, escape_char: impl Fn(char) -> Option<&'static str>) -> Cow<'_, str> {
+    if !input.chars().any(|c| escape_char(c).is_some()) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut escaped = String::with_capacity(input.len());
+
+    for c in input.chars() {
+        match escape_char(c) {
+            Some(entity) => escaped.push_str(entity),
+            None => escaped.push(c),
+        }
+    }
+
+    Cow::Owned(escaped)
+}
+
+/// Escape `&`, `<` and `>` so the text can not open tags or entities.
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    escape(text, |c| match c {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+/// Escape `&` and `"` so the value can not end the double quoted attribute.
+pub fn escape_attribute_value(value: &str) -> Cow<'_, str> {
+    escape(value, |c| match c {
+        '&' => Some("&amp;"),
+        '"' => Some("&quot;"),
+        _ => None,
+    })
+}
+
+/// Escape all characters with a predefined entity in XML, so the value is valid in any quoting.
+pub fn escape_xml_attribute_value(value: &str) -> Cow<'_, str> {
+    escape(value, |c| match c {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        '"' => Some("&quot;"),
+        '\'' => Some("&apos;"),
+        _ => None,
+    })
+}
+
+/// Wrap the text in a CDATA section, a `]]>` inside of it is split across two sections.
+pub fn escape_cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// Separate consecutive dashes, so the text can neither close the comment nor contain `--`.
+///
+/// A leading `>` or `->` and a trailing `-` are padded with a space, they would close the comment as well.
+pub fn escape_comment(text: &str) -> Cow<'_, str> {
+    let is_padded_start = text.starts_with('>') || text.starts_with("->");
+    let is_padded_end = text.ends_with('-');
+
+    if !text.contains("--") && !is_padded_start && !is_padded_end {
+        return Cow::Borrowed(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len() + 2);
+    let mut previous_char = None;
+
+    if is_padded_start {
+        escaped.push(' ');
+    }
+
+    for c in text.chars() {
+        if c == '-' && previous_char == Some('-') {
+            escaped.push(' ');
+        }
+
+        escaped.push(c);
+        previous_char = Some(c);
+    }
+
+    if is_padded_end {
+        escaped.push(' ');
+    }
+
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::compiler::escape::{
+        escape_attribute_value, escape_cdata, escape_comment, escape_text,
+        escape_xml_attribute_value,
+    };
+
+    #[test]
+    fn it_should_escape_text() {
+        assert_eq!(
+            escape_text(r#"a < b && c > "d""#),
+            r#"a &lt; b &amp;&amp; c &gt; "d""#
+        );
+    }
+
+    #[test]
+    fn it_should_escape_attribute_value() {
+        assert_eq!(
+            escape_attribute_value(r#"a < b && "c""#),
+            "a < b &amp;&amp; &quot;c&quot;"
+        );
+    }
+
+    #[test]
+    fn it_should_escape_xml_attribute_value() {
+        assert_eq!(
+            escape_xml_attribute_value(r#"<a href='x'>"&"</a>"#),
+            "&lt;a href=&apos;x&apos;&gt;&quot;&amp;&quot;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn it_should_escape_cdata() {
+        assert_eq!(
+            escape_cdata("if (a < b && c[d[0]]>1) {}"),
+            "<![CDATA[if (a < b && c[d[0]]]]><![CDATA[>1) {}]]>"
+        );
+    }
+
+    #[test]
+    fn it_should_escape_comment() {
+        assert_eq!(escape_comment(" a --> b ---"), " a - -> b - - - ");
+    }
+
+    #[test]
+    fn it_should_escape_comment_boundaries() {
+        assert_eq!(escape_comment("> x"), " > x");
+        assert_eq!(escape_comment("-> x"), " -> x");
+        assert_eq!(escape_comment(" x -"), " x - ");
+        assert_eq!(escape_comment(" x <!-"), " x <!- ");
+    }
+
+    #[test]
+    fn it_should_borrow_if_nothing_is_escaped() {
+        assert!(matches!(escape_text("Hello World"), Cow::Borrowed(_)));
+        assert!(matches!(escape_attribute_value("a-b"), Cow::Borrowed(_)));
+        assert!(matches!(escape_comment(" a - b"), Cow::Borrowed(_)));
+    }
+}
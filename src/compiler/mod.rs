@@ -1,25 +1,258 @@
+use serde::Deserialize;
+
 use crate::parser::{
-    HsmlNode, RootNode, attribute::node::AttributeNode, comment::node::CommentNode,
-    tag::node::TagNode,
+    HsmlNode, RootNode,
+    attribute::node::AttributeNode,
+    comment::node::CommentNode,
+    doctype::node::DoctypeNode,
+    tag::node::{RAW_TEXT_ELEMENTS, TagNode},
+    text::node::TextNode,
+};
+
+use self::{
+    error::HsmlCompileError,
+    escape::{
+        escape_attribute_value, escape_cdata, escape_comment, escape_text,
+        escape_xml_attribute_value,
+    },
 };
 
-#[derive(Default)]
-pub struct HsmlCompileOptions {}
+pub mod error;
+pub mod escape;
+
+/// Elements that can not have any content and have no closing tag.
+///
+/// See https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+pub const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// The dialect of the generated markup.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HsmlOutputMode {
+    /// Void elements without closing tag and bare boolean attributes like `<input disabled>`
+    #[default]
+    Html5,
+    /// Self-closing void elements and boolean attributes with their name as value like `<input disabled="disabled" />`
+    Xhtml,
+    /// Every empty element is self-closing, there are no void elements and attribute values escape `<`, `>` and `'` as well
+    Xml,
+}
+
+/// Elements that are rendered inline, a line break around them would add visible whitespace.
+///
+/// Every other element, including unknown ones like components, is treated as block element.
+pub const INLINE_ELEMENTS: [&str; 44] = [
+    "a", "abbr", "acronym", "audio", "b", "bdi", "bdo", "big", "br", "button", "canvas", "cite",
+    "code", "data", "dfn", "em", "embed", "i", "iframe", "img", "input", "kbd", "label", "map",
+    "mark", "meter", "object", "output", "picture", "progress", "q", "ruby", "s", "samp", "select",
+    "small", "span", "strong", "sub", "sup", "svg", "textarea", "time", "u",
+];
+
+/// Elements whose content is never reformatted.
+pub const WHITESPACE_SENSITIVE_ELEMENTS: [&str; 2] = ["pre", "textarea"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HsmlIndentStyle {
+    #[default]
+    Spaces,
+    Tabs,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HsmlNewline {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct HsmlPrettyOptions {
+    pub indent_style: HsmlIndentStyle,
+    /// Number of spaces per indentation level, ignored for tabs
+    pub indent_width: usize,
+    pub newline: HsmlNewline,
+}
+
+impl Default for HsmlPrettyOptions {
+    fn default() -> Self {
+        Self {
+            indent_style: HsmlIndentStyle::default(),
+            indent_width: 2,
+            newline: HsmlNewline::default(),
+        }
+    }
+}
+
+impl HsmlPrettyOptions {
+    fn indent(&self, level: usize) -> String {
+        match self.indent_style {
+            HsmlIndentStyle::Spaces => " ".repeat(self.indent_width * level),
+            HsmlIndentStyle::Tabs => "\t".repeat(level),
+        }
+    }
+
+    fn newline(&self) -> &'static str {
+        match self.newline {
+            HsmlNewline::Lf => "\n",
+            HsmlNewline::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Deserializes from camelCase keys like `{ mode: "xhtml", pretty: { indentWidth: 4 } }`, missing keys keep their default.
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct HsmlCompileOptions {
+    pub mode: HsmlOutputMode,
+    /// Use the output mode that matches the `doctype` of the source instead of `mode`, if there is one
+    ///
+    /// The doctype takes precedence over `mode`, disable this to force an explicitly chosen `mode`.
+    pub mode_from_doctype: bool,
+    /// Put block elements on their own indented lines, everything is written on a single line if `None`
+    pub pretty: Option<HsmlPrettyOptions>,
+    /// Escape `&`, `<` and `>` in text, text written with `!=` is never escaped
+    pub escape_text: bool,
+    /// Escape `&` and `"` in ids, classes and attribute values, values written with `!=` are never escaped
+    pub escape_attribute_values: bool,
+    /// Separate consecutive dashes in native comments, so they can not end the comment early
+    pub escape_comments: bool,
+    /// Close void elements like `<br />` instead of `<br>`, only affects [`HsmlOutputMode::Html5`]
+    pub self_closing_void_elements: bool,
+}
+
+impl Default for HsmlCompileOptions {
+    fn default() -> Self {
+        Self {
+            mode: HsmlOutputMode::default(),
+            mode_from_doctype: true,
+            pretty: None,
+            escape_text: true,
+            escape_attribute_values: true,
+            escape_comments: true,
+            self_closing_void_elements: false,
+        }
+    }
+}
+
+fn push_attribute_value(
+    html_content: &mut String,
+    value: &str,
+    is_unescaped: bool,
+    options: &HsmlCompileOptions,
+) {
+    html_content.push_str(r#"=""#);
+
+    if !options.escape_attribute_values || is_unescaped {
+        html_content.push_str(value);
+    } else if options.mode == HsmlOutputMode::Xml {
+        html_content.push_str(&escape_xml_attribute_value(value));
+    } else {
+        html_content.push_str(&escape_attribute_value(value));
+    }
+
+    html_content.push('"');
+}
+
+/// Whether a line break before and after the node does not change the rendered document.
+fn is_block_node(node: &HsmlNode) -> bool {
+    match node {
+        HsmlNode::Tag(tag_node) => !INLINE_ELEMENTS.contains(&tag_node.tag.as_str()),
+        HsmlNode::Doctype(_) | HsmlNode::Comment(_) => true,
+        _ => false,
+    }
+}
+
+/// Whether the nodes are broken into lines, which only changes the layout if there is a block node among them.
+fn is_breakable(nodes: &[HsmlNode], options: &HsmlCompileOptions) -> bool {
+    options.pretty.is_some() && nodes.iter().any(is_block_node)
+}
+
+/// `level` is the indentation level if the nodes are broken into lines, otherwise they are written in a row.
+///
+/// Every block node goes on its own line, adjacent inline nodes share one, because a line break between them would
+/// add whitespace to the rendered document.
+///
+/// Adjacent text nodes are separated by a line break, `follows_text` tells whether text directly precedes the nodes.
+fn compile_nodes(
+    nodes: &[HsmlNode],
+    level: Option<usize>,
+    follows_text: bool,
+    options: &HsmlCompileOptions,
+) -> Result<String, HsmlCompileError> {
+    let mut html_content = String::new();
+    let mut previous_is_text = follows_text;
+    let mut is_inline_line = false;
+
+    for node in nodes {
+        let node_content = compile_node(node, level, options)?;
+
+        // dev comments produce no output and do not separate text
+        if node_content.is_empty() {
+            continue;
+        }
+
+        let is_text = matches!(node, HsmlNode::Text(_));
+        if is_text && previous_is_text {
+            html_content.push('\n');
+        }
+        previous_is_text = is_text;
+
+        match (level, &options.pretty) {
+            (Some(level), Some(pretty)) => {
+                let is_block = is_block_node(node);
+
+                if is_block && is_inline_line {
+                    html_content.push_str(pretty.newline());
+                }
+
+                if is_block || !is_inline_line {
+                    html_content.push_str(&pretty.indent(level));
+                }
+
+                html_content.push_str(&node_content);
 
-fn compile_tag_node(tag_node: &TagNode, _options: &HsmlCompileOptions) -> String {
+                if is_block {
+                    html_content.push_str(pretty.newline());
+                }
+
+                is_inline_line = !is_block;
+            }
+            _ => html_content.push_str(&node_content),
+        }
+    }
+
+    if let Some(pretty) = level.and(options.pretty.as_ref())
+        && is_inline_line
+    {
+        html_content.push_str(pretty.newline());
+    }
+
+    Ok(html_content)
+}
+
+fn compile_tag_node(
+    tag_node: &TagNode,
+    level: Option<usize>,
+    options: &HsmlCompileOptions,
+) -> Result<String, HsmlCompileError> {
     let mut html_content = String::new();
 
     html_content.push('<');
     html_content.push_str(&tag_node.tag);
 
     if let Some(id_node) = &tag_node.id {
-        html_content.push_str(r#" id=""#);
-        html_content.push_str(&id_node.id);
-        html_content.push('\"');
+        html_content.push_str(" id");
+        push_attribute_value(&mut html_content, &id_node.id, false, options);
     }
 
     if let Some(class_nodes) = &tag_node.classes {
-        html_content.push_str(r#" class=""#);
+        html_content.push_str(" class");
 
         let class_names: String = class_nodes
             .iter()
@@ -27,55 +260,116 @@ fn compile_tag_node(tag_node: &TagNode, _options: &HsmlCompileOptions) -> String
             .collect::<Vec<&str>>()
             .join(" ");
 
-        html_content.push_str(&class_names);
-
-        html_content.push('\"');
+        push_attribute_value(&mut html_content, &class_names, false, options);
     }
 
     if let Some(attributes) = &tag_node.attributes {
-        attributes.iter().for_each(|node| match node {
-            HsmlNode::Attribute(AttributeNode { key, value }) => {
-                html_content.push(' ');
-                html_content.push_str(key);
-
-                if let Some(value) = value {
-                    html_content.push_str(r#"=""#);
-                    html_content.push_str(value);
-                    html_content.push('\"');
+        for node in attributes {
+            match node {
+                HsmlNode::Attribute(AttributeNode {
+                    key,
+                    value,
+                    is_unescaped,
+                    ..
+                }) => {
+                    html_content.push(' ');
+                    html_content.push_str(key);
+
+                    match value {
+                        Some(value) => {
+                            push_attribute_value(&mut html_content, value, *is_unescaped, options)
+                        }
+                        // boolean attributes need a value outside of html
+                        None if options.mode != HsmlOutputMode::Html5 => {
+                            push_attribute_value(&mut html_content, key, false, options)
+                        }
+                        None => {}
+                    }
                 }
+                HsmlNode::Comment(node) if node.is_dev => {
+                    // do nothing
+                }
+                _ => return Err(unsupported_node(node)),
             }
-            HsmlNode::Comment(node) if node.is_dev => {
-                // do nothing
-            }
-            _ => panic!("Unsupported node type"),
-        });
+        }
+    }
+
+    let is_void_element =
+        options.mode != HsmlOutputMode::Xml && VOID_ELEMENTS.contains(&tag_node.tag.as_str());
+
+    if is_void_element {
+        // report the first content of the void element
+        let content_span = tag_node
+            .text
+            .as_ref()
+            .map(|text| text.span)
+            .or_else(|| tag_node.children.iter().flatten().find_map(HsmlNode::span));
+
+        if let Some(span) = content_span {
+            return Err(HsmlCompileError::VoidElementContent {
+                tag: tag_node.tag.clone(),
+                position: span.start,
+            });
+        }
+
+        let is_self_closing =
+            options.mode == HsmlOutputMode::Xhtml || options.self_closing_void_elements;
+
+        html_content.push_str(if is_self_closing { " />" } else { ">" });
+
+        return Ok(html_content);
     }
 
-    let should_auto_close = tag_node.children.is_none() && tag_node.text.is_none();
-    if should_auto_close {
+    if options.mode == HsmlOutputMode::Xml && tag_node.text.is_none() && tag_node.children.is_none()
+    {
         html_content.push_str("/>");
-        return html_content;
-    } else {
-        html_content.push('>');
+
+        return Ok(html_content);
     }
 
-    if let Some(text) = &tag_node.text {
-        html_content.push_str(&text.text);
+    html_content.push('>');
+
+    if let Some(text_node) = &tag_node.text {
+        if RAW_TEXT_ELEMENTS.contains(&tag_node.tag.as_str()) {
+            let is_escaped = options.escape_text && !text_node.is_unescaped;
+
+            if options.mode == HsmlOutputMode::Xml && is_escaped && !text_node.text.is_empty() {
+                // xml has no raw text elements, but a CDATA section keeps scripts and styles readable
+                html_content.push_str(&escape_cdata(&text_node.text));
+            } else {
+                // escaping would break scripts and styles
+                html_content.push_str(&text_node.text);
+            }
+        } else {
+            html_content.push_str(&compile_text_node(text_node, options)?);
+        }
     }
 
     if let Some(child_nodes) = &tag_node.children {
-        for child_node in child_nodes {
-            match child_node {
-                HsmlNode::Tag(tag_node) => {
-                    html_content.push_str(&compile_tag_node(tag_node, _options))
-                }
-                HsmlNode::Comment(comment_node) => {
-                    if !comment_node.is_dev {
-                        html_content.push_str(&compile_comment_node(comment_node, _options))
-                    }
-                }
-                _ => panic!("Unsupported node type"),
+        // text and whitespace sensitive content keep the children in a row
+        let child_level = level.filter(|_| {
+            tag_node.text.is_none()
+                && !WHITESPACE_SENSITIVE_ELEMENTS.contains(&tag_node.tag.as_str())
+                && is_breakable(child_nodes, options)
+        });
+
+        match (child_level, &options.pretty) {
+            (Some(level), Some(pretty)) => {
+                html_content.push_str(pretty.newline());
+                html_content.push_str(&compile_nodes(
+                    child_nodes,
+                    Some(level + 1),
+                    false,
+                    options,
+                )?);
+                html_content.push_str(&pretty.indent(level));
             }
+            _ => html_content.push_str(&compile_nodes(
+                child_nodes,
+                None,
+                tag_node.text.is_some(),
+                options,
+            )?),
         }
     }
 
@@ -83,46 +377,214 @@ fn compile_tag_node(tag_node: &TagNode, _options: &HsmlCompileOptions) -> String
     html_content.push_str(&tag_node.tag);
     html_content.push('>');
 
-    html_content
+    Ok(html_content)
+}
+
+/// Interpolated tags are compiled inline, the text around them is escaped like the text node itself.
+fn compile_text_node(
+    text_node: &TextNode,
+    options: &HsmlCompileOptions,
+) -> Result<String, HsmlCompileError> {
+    let is_escaped = options.escape_text && !text_node.is_unescaped;
+
+    let Some(segments) = &text_node.segments else {
+        return Ok(if is_escaped {
+            escape_text(&text_node.text).into_owned()
+        } else {
+            text_node.text.clone()
+        });
+    };
+
+    let mut html_content = String::new();
+
+    for segment in segments {
+        match segment {
+            HsmlNode::Text(text_segment) if is_escaped => {
+                html_content.push_str(&escape_text(&text_segment.text));
+            }
+            HsmlNode::Text(text_segment) => html_content.push_str(&text_segment.text),
+            HsmlNode::Tag(tag_node) => {
+                html_content.push_str(&compile_tag_node(tag_node, None, options)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(html_content)
 }
 
-fn compile_comment_node(comment_node: &CommentNode, _options: &HsmlCompileOptions) -> String {
+fn compile_comment_node(comment_node: &CommentNode, options: &HsmlCompileOptions) -> String {
     let mut html_content = String::new();
 
     html_content.push_str("<!--");
-    html_content.push_str(&comment_node.text);
+
+    if options.escape_comments {
+        html_content.push_str(&escape_comment(&comment_node.text));
+    } else {
+        html_content.push_str(&comment_node.text);
+    }
+
     html_content.push_str(" -->");
 
     html_content
 }
 
-fn compile_node(node: &HsmlNode, options: &HsmlCompileOptions) -> String {
+/// Doctype values that are expanded by [`resolve_doctype`].
+const DOCTYPE_SHORTCUTS: [&str; 9] = [
+    "html",
+    "xml",
+    "transitional",
+    "strict",
+    "frameset",
+    "1.1",
+    "basic",
+    "mobile",
+    "plist",
+];
+
+/// The declaration and the matching output mode of a doctype.
+///
+/// Shortcuts like `strict` are expanded case-insensitively, every other value is written as `<!DOCTYPE value>` and compiled as xhtml.
+fn resolve_doctype(value: &str) -> (String, HsmlOutputMode) {
+    let public =
+        |identifier: &str, dtd: &str| format!(r#"<!DOCTYPE html PUBLIC "{identifier}" "{dtd}">"#);
+
+    let shortcut = DOCTYPE_SHORTCUTS
+        .iter()
+        .find(|shortcut| shortcut.eq_ignore_ascii_case(value));
+
+    match shortcut.copied().unwrap_or(value) {
+        "html" => (String::from("<!DOCTYPE html>"), HsmlOutputMode::Html5),
+        "xml" => (
+            String::from(r#"<?xml version="1.0" encoding="utf-8" ?>"#),
+            HsmlOutputMode::Xml,
+        ),
+        "transitional" => (
+            public(
+                "-//W3C//DTD XHTML 1.0 Transitional//EN",
+                "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd",
+            ),
+            HsmlOutputMode::Xhtml,
+        ),
+        "strict" => (
+            public(
+                "-//W3C//DTD XHTML 1.0 Strict//EN",
+                "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd",
+            ),
+            HsmlOutputMode::Xhtml,
+        ),
+        "frameset" => (
+            public(
+                "-//W3C//DTD XHTML 1.0 Frameset//EN",
+                "http://www.w3.org/TR/xhtml1/DTD/xhtml1-frameset.dtd",
+            ),
+            HsmlOutputMode::Xhtml,
+        ),
+        "1.1" => (
+            public(
+                "-//W3C//DTD XHTML 1.1//EN",
+                "http://www.w3.org/TR/xhtml11/DTD/xhtml11.dtd",
+            ),
+            HsmlOutputMode::Xhtml,
+        ),
+        "basic" => (
+            public(
+                "-//W3C//DTD XHTML Basic 1.1//EN",
+                "http://www.w3.org/TR/xhtml-basic/xhtml-basic11.dtd",
+            ),
+            HsmlOutputMode::Xhtml,
+        ),
+        "mobile" => (
+            public(
+                "-//WAPFORUM//DTD XHTML Mobile 1.2//EN",
+                "http://www.openmobilealliance.org/tech/DTD/xhtml-mobile12.dtd",
+            ),
+            HsmlOutputMode::Xhtml,
+        ),
+        "plist" => (
+            String::from(
+                r#"<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">"#,
+            ),
+            HsmlOutputMode::Xml,
+        ),
+        _ => (format!("<!DOCTYPE {value}>"), HsmlOutputMode::Xhtml),
+    }
+}
+
+fn compile_doctype_node(doctype_node: &DoctypeNode) -> String {
+    let (declaration, _) = resolve_doctype(&doctype_node.value);
+
+    declaration
+}
+
+fn unsupported_node(node: &HsmlNode) -> HsmlCompileError {
+    HsmlCompileError::UnsupportedNode {
+        position: node.span().map(|span| span.start).unwrap_or_default(),
+    }
+}
+
+fn compile_node(
+    node: &HsmlNode,
+    level: Option<usize>,
+    options: &HsmlCompileOptions,
+) -> Result<String, HsmlCompileError> {
     match node {
-        HsmlNode::Tag(tag_node) => compile_tag_node(tag_node, options),
+        HsmlNode::Doctype(doctype_node) => Ok(compile_doctype_node(doctype_node)),
+        HsmlNode::Tag(tag_node) => compile_tag_node(tag_node, level, options),
+        HsmlNode::Text(text_node) => compile_text_node(text_node, options),
         HsmlNode::Comment(comment_node) if !comment_node.is_dev => {
-            compile_comment_node(comment_node, options)
+            Ok(compile_comment_node(comment_node, options))
         }
-        HsmlNode::Comment(_) => String::from(""),
-        _ => panic!("Unsupported node type"),
+        HsmlNode::Comment(_) => Ok(String::from("")),
+        HsmlNode::RawHtml(raw_html_node) => Ok(raw_html_node.html.clone()),
+        _ => Err(unsupported_node(node)),
     }
 }
 
-pub fn compile(hsml_ast: &RootNode, options: &HsmlCompileOptions) -> String {
-    let mut html_content = String::new();
+pub fn compile(
+    hsml_ast: &RootNode,
+    options: &HsmlCompileOptions,
+) -> Result<String, HsmlCompileError> {
+    let doctype_mode = hsml_ast
+        .nodes
+        .iter()
+        .find_map(|node| match node {
+            HsmlNode::Doctype(doctype_node) => Some(resolve_doctype(&doctype_node.value).1),
+            _ => None,
+        })
+        .filter(|_| options.mode_from_doctype);
 
-    for node in &hsml_ast.nodes {
-        html_content.push_str(&compile_node(node, options));
-    }
+    let doctype_options;
+    let options = match doctype_mode {
+        Some(mode) => {
+            doctype_options = HsmlCompileOptions {
+                mode,
+                ..options.clone()
+            };
+            &doctype_options
+        }
+        None => options,
+    };
 
-    html_content
+    // the root is always broken into lines, so pretty output ends with a newline
+    let level = options.pretty.is_some().then_some(0);
+
+    compile_nodes(&hsml_ast.nodes, level, false, options)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        compiler::{HsmlCompileOptions, compile},
+        compiler::{
+            HsmlCompileOptions, HsmlIndentStyle, HsmlNewline, HsmlOutputMode, HsmlPrettyOptions,
+            compile, error::HsmlCompileError,
+        },
         parser::{
-            HsmlNode, RootNode, id::node::IdNode, parse::parse, tag::node::TagNode,
+            HsmlNode, RootNode,
+            id::node::IdNode,
+            parse::parse,
+            span::{Position, Span},
+            tag::node::TagNode,
             text::node::TextNode,
         },
     };
@@ -131,7 +593,7 @@ mod tests {
     fn it_should_compile_empty_ast() {
         let ast = RootNode { nodes: vec![] };
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(html_content, "");
     }
@@ -146,12 +608,18 @@ mod tests {
                 attributes: None,
                 text: Some(TextNode {
                     text: String::from("Hello World"),
+                    raw_text: String::from("Hello World"),
+                    is_unescaped: false,
+                    segments: None,
+                    span: Span::default(),
                 }),
                 children: None,
+                is_block_expansion: false,
+                span: Span::default(),
             })],
         };
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(html_content, "<h1>Hello World</h1>");
     }
@@ -163,17 +631,24 @@ mod tests {
                 tag: String::from("h1"),
                 id: Some(IdNode {
                     id: String::from("title"),
+                    span: Span::default(),
                 }),
                 classes: None,
                 attributes: None,
                 text: Some(TextNode {
                     text: String::from("Hello World"),
+                    raw_text: String::from("Hello World"),
+                    is_unescaped: false,
+                    segments: None,
+                    span: Span::default(),
                 }),
                 children: None,
+                is_block_expansion: false,
+                span: Span::default(),
             })],
         };
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(html_content, r#"<h1 id="title">Hello World</h1>"#);
     }
@@ -189,15 +664,14 @@ mod tests {
   .card__body {{ fullName }}
 "#;
 
-        let (rest, ast) = parse(input).unwrap();
+        let ast = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(
             html_content,
-            r#"<h1 class="text-red">Vite CJS Faker Demo</h1><div class="card"><div class="card__image"><img :src="natureImageUrl" :alt="'Background image for ' + fullName"/></div><div class="card__profile"><img :src="avatarUrl" :alt="'Avatar image of ' + fullName"/></div><div class="card__body">{{ fullName }}</div></div>"#
+            r#"<h1 class="text-red">Vite CJS Faker Demo</h1><div class="card"><div class="card__image"><img :src="natureImageUrl" :alt="'Background image for ' + fullName"></div><div class="card__profile"><img :src="avatarUrl" :alt="'Avatar image of ' + fullName"></div><div class="card__body">{{ fullName }}</div></div>"#
         );
-        assert_eq!(rest, "");
     }
 
     #[test]
@@ -225,17 +699,16 @@ figure.md:flex.bg-slate-100.rounded-xl.p-8.md:p-0.dark:bg-slate-800/10
         Staff Engineer, Algolia
 "#;
 
-        let (rest, ast) = parse(input).unwrap();
+        let ast = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(
             html_content,
-            r#"<!-- test comment on root layer --><figure class="md:flex bg-slate-100 rounded-xl p-8 md:p-0 dark:bg-slate-800/10"><!-- test comment --><img class="w-24 h-24 md:w-48 md:h-auto md:rounded-none rounded-full mx-auto" src="/fancy-avatar.jpg" alt="" width="384" height="512"/><div class="pt-6 md:p-8 text-center md:text-left space-y-4"><blockquote v-if="showBlockquote"><p class="text-lg font-medium">"Tailwind CSS is the only framework that I've seen scale
+            r#"<!-- test comment on root layer --><figure class="md:flex bg-slate-100 rounded-xl p-8 md:p-0 dark:bg-slate-800/10"><!-- test comment --><img class="w-24 h-24 md:w-48 md:h-auto md:rounded-none rounded-full mx-auto" src="/fancy-avatar.jpg" alt="" width="384" height="512"><div class="pt-6 md:p-8 text-center md:text-left space-y-4"><blockquote v-if="showBlockquote"><p class="text-lg font-medium">"Tailwind CSS is the only framework that I've seen scale
 on large teams. It's easy to customize, adapts to any design,
 and the build size is tiny."</p></blockquote><figcaption class="font-medium"><div class="text-sky-500 dark:text-sky-400">Sarah Dayan</div><div class="text-[#af05c9] dark:text-slate-500">Staff Engineer, Algolia</div></figcaption></div></figure>"#
         );
-        assert_eq!(rest, "");
     }
 
     #[test]
@@ -274,18 +747,17 @@ and the build size is tiny."</p></blockquote><figcaption class="font-medium"><di
     div(v-if="isDM")
 "#;
 
-        let (rest, ast) = parse(input).unwrap();
+        let ast = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(
             html_content,
             r#"<div class="space-y-3" :class="{
     'pt2 pb0.5 px3.5 bg-dm rounded-4 me--1': isDM,
-    'ms--3.5 mt--1 ms--1': isDM && context !== 'details',
-  }"><StatusBody v-if="(!isFiltered && isSensitiveNonSpoiler) || hideAllMedia" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"/><StatusSpoiler :enabled="hasSpoilerOrSensitiveMedia || isFiltered" :filter="isFiltered" :sensitive-non-spoiler="isSensitiveNonSpoiler || hideAllMedia" :is-d-m="isDM"><template v-if="spoilerTextPresent" #spoiler><p>{{ status.spoilerText }}</p></template><template v-else-if="filterPhrase" #spoiler><p>{{ `${$t('status.filter_hidden_phrase')}: ${filterPhrase}` }}</p></template><StatusBody v-if="!(isSensitiveNonSpoiler || hideAllMedia)" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"/><StatusTranslation :status="status"/><StatusPoll v-if="status.poll" :status="status"/><StatusMedia v-if="status.mediaAttachments?.length" :status="status" :is-preview="isPreview"/><StatusPreviewCard v-if="status.card" :card="status.card" :small-picture-only="status.mediaAttachments?.length > 0"/><StatusCard v-if="status.reblog" :status="status.reblog" border="~ rounded" :actions="false"/><div v-if="isDM"/></StatusSpoiler></div>"#
+    'ms--3.5 mt--1 ms--1': isDM &amp;&amp; context !== 'details',
+  }"><StatusBody v-if="(!isFiltered &amp;&amp; isSensitiveNonSpoiler) || hideAllMedia" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"></StatusBody><StatusSpoiler :enabled="hasSpoilerOrSensitiveMedia || isFiltered" :filter="isFiltered" :sensitive-non-spoiler="isSensitiveNonSpoiler || hideAllMedia" :is-d-m="isDM"><template v-if="spoilerTextPresent" #spoiler><p>{{ status.spoilerText }}</p></template><template v-else-if="filterPhrase" #spoiler><p>{{ `${$t('status.filter_hidden_phrase')}: ${filterPhrase}` }}</p></template><StatusBody v-if="!(isSensitiveNonSpoiler || hideAllMedia)" :status="status" :newer="newer" :with-action="!isDetails" :class="isDetails ? 'text-xl' : ''"></StatusBody><StatusTranslation :status="status"></StatusTranslation><StatusPoll v-if="status.poll" :status="status"></StatusPoll><StatusMedia v-if="status.mediaAttachments?.length" :status="status" :is-preview="isPreview"></StatusMedia><StatusPreviewCard v-if="status.card" :card="status.card" :small-picture-only="status.mediaAttachments?.length > 0"></StatusPreviewCard><StatusCard v-if="status.reblog" :status="status.reblog" border="~ rounded" :actions="false"></StatusCard><div v-if="isDM"></div></StatusSpoiler></div>"#
         );
-        assert_eq!(rest, "");
     }
 
     #[test]
@@ -323,14 +795,680 @@ and the build size is tiny."</p></blockquote><figcaption class="font-medium"><di
     slot
 "#;
 
-        let (rest, ast) = parse(input).unwrap();
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<div ref="container" :class="containerClass"><div class="sticky top-0 z10 backdrop-blur native:lg:w-[calc(100vw-5rem)] native:xl:w-[calc(135%+(100vw-1200px)/2)]" pt="[env(safe-area-inset-top,0)]" bg="[rgba(var(--rgb-bg-base),0.7)]"><div class="flex justify-between px5 py2 native:xl:flex" :class="{ 'xl:hidden': $route.name !== 'tag' }" border="b base"><div class="flex gap-3 items-center py2 w-full" :overflow-hidden="!noOverflowHidden ? '' : false"><NuxtLink class="items-center btn-text p-0 xl:hidden" v-if="backOnSmallScreen || back" flex="~ gap1" :aria-label="$t('nav.back')" @click="$router.go(-1)"><div class="rtl-flip" i-ri:arrow-left-line></div></NuxtLink><div class="flex w-full native-mac:justify-center native-mac:text-center native-mac:sm:justify-start" :truncate="!noOverflowHidden ? '' : false" data-tauri-drag-region><slot name="title"></slot></div><div class="sm:hidde nh-7 w-1px"></div></div><div class="flex items-center flex-shrink-0 gap-x-2"><slot name="actions"></slot><PwaBadge class="lg:hidden"></PwaBadge><NavUser v-if="isHydrated"></NavUser><NavUserSkeleton v-else></NavUserSkeleton></div></div><slot name="header"><div hidden></div></slot></div><PwaInstallPrompt class="lg:hidden"></PwaInstallPrompt><div class="m-auto" :class="isHydrated &amp;&amp; wideLayout ? 'xl:w-full sm:max-w-600px' : 'sm:max-w-600px md:shrink-0'"><div class="h-6" hidden :class="{ 'xl:block': $route.name !== 'tag' &amp;&amp; !$slots.header }"></div><slot></slot></div></div>"#
+        );
+    }
+
+    #[test]
+    fn it_should_escape_text_and_attribute_values() {
+        let input = r#"p(title="Tom & \"Jerry\"") 1 < 2 && 3 > 2
+//! a --> b
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<p title="Tom &amp; &quot;Jerry&quot;">1 &lt; 2 &amp;&amp; 3 &gt; 2</p><!-- a - -> b -->"#
+        );
+    }
+
+    #[test]
+    fn it_should_escape_comment_boundaries() {
+        let input = "//!> x\n//!-> y\n//! z -\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(html_content, "<!-- > x --><!-- -> y --><!-- z -  -->");
+    }
+
+    #[test]
+    fn it_should_not_escape_unescaped_nodes() {
+        let input = r#"p(title!="<b>&amp;</b>")!= <b>bold</b> &amp; raw
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<p title="<b>&amp;</b>"><b>bold</b> &amp; raw</p>"#
+        );
+    }
+
+    #[test]
+    fn it_should_not_escape_if_disabled() {
+        let input = r#"p(:class="a && b") {{ a && b }}
+//! a -- b
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                escape_text: false,
+                escape_attribute_values: false,
+                escape_comments: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<p :class="a && b">{{ a && b }}</p><!-- a -- b -->"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_void_elements() {
+        let input = "div\n  br\n  img(src=\"a.png\" alt=\"\")\n  span\n";
+
+        let ast = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<div><br><img src="a.png" alt=""><span></span></div>"#
+        );
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    self_closing_void_elements: true,
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            r#"<div><br /><img src="a.png" alt="" /><span></span></div>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_crlf_line_endings() {
+        let input =
+            "div\r\n  p Hello\r\n  span\r\n  //! c\r\n  p.\r\n    line1 #[b x]\r\n    line2\r\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            "<div><p>Hello</p><span></span><!-- c --><p>line1 <b>x</b>\nline2</p></div>"
+        );
+    }
+
+    #[test]
+    fn it_should_compile_without_trailing_newline() {
+        let inputs = [
+            "h1 Hello",
+            "p Hello #[b world]",
+            "// dev comment",
+            "//! native comment",
+            "div\n  p Hi\n  // dev comment",
+            "img(src=\"a.png\" alt)",
+            "img(\n  src=\"a.png\" // comment\n)",
+            "p.\n  first line\n  second line",
+            "p.",
+            "script.\n  a();",
+            "p\n  | pipe text",
+            "ul\n  li: a(href=\"/\") Home",
+            "div\n  <\n    <b>raw</b>",
+        ];
+
+        for input in inputs {
+            let with_newline = format!("{input}\n");
+
+            assert_eq!(
+                compile(&parse(input).unwrap(), &HsmlCompileOptions::default()),
+                compile(
+                    &parse(&with_newline).unwrap(),
+                    &HsmlCompileOptions::default()
+                ),
+                "input: {input:?}"
+            );
+        }
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_compile_void_element_with_text() {
+        let input = "div\n  br Text\n";
+
+        let ast = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()),
+            Err(HsmlCompileError::VoidElementContent {
+                tag: String::from("br"),
+                position: Position::new(9, 2, 6),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_not_compile_void_element_with_children() {
+        let input = "input\n  span\n";
+
+        let ast = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()),
+            Err(HsmlCompileError::VoidElementContent {
+                tag: String::from("input"),
+                position: Position::new(8, 2, 3),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_not_compile_unsupported_node() {
+        let ast = RootNode {
+            nodes: vec![HsmlNode::Id(IdNode {
+                id: String::from("main"),
+                span: Span::new(Position::new(3, 1, 4), Position::new(8, 1, 9)),
+            })],
+        };
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()),
+            Err(HsmlCompileError::UnsupportedNode {
+                position: Position::new(3, 1, 4),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_compile_xhtml() {
+        let input = "form\n  input(type=\"checkbox\" checked)\n  br\n  div\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                mode: HsmlOutputMode::Xhtml,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<form><input type="checkbox" checked="checked" /><br /><div></div></form>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_xml() {
+        let input = "feed(title=\"Tom's <news>\")\n  entry(draft)\n  br\n  link Text\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                mode: HsmlOutputMode::Xml,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<feed title="Tom&apos;s &lt;news&gt;"><entry draft="draft"/><br/><link>Text</link></feed>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_pretty() {
+        let input = r#"//! layout
+.card
+  h1.title Hello
+  p Some <text>
+  ul
+    li
+      a(href="/") Home
+    li
+  .card__image
+    img(src="a.png" alt="")
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                pretty: Some(HsmlPrettyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<!-- layout -->
+<div class="card">
+  <h1 class="title">Hello</h1>
+  <p>Some &lt;text&gt;</p>
+  <ul>
+    <li><a href="/">Home</a></li>
+    <li></li>
+  </ul>
+  <div class="card__image"><img src="a.png" alt=""></div>
+</div>
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_pretty_with_tabs_and_crlf() {
+        let input = "div\n  div\n    pre\n      div\n    span\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                pretty: Some(HsmlPrettyOptions {
+                    indent_style: HsmlIndentStyle::Tabs,
+                    newline: HsmlNewline::CrLf,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            "<div>\r\n\t<div>\r\n\t\t<pre><div></div></pre>\r\n\t\t<span></span>\r\n\t</div>\r\n</div>\r\n"
+        );
+    }
+
+    #[test]
+    fn it_should_compile_pretty_with_inline_siblings() {
+        let input = "div\n  p Text\n  textarea.\n    line\n  b bold\n  | text\n  pre code\nbr\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                pretty: Some(HsmlPrettyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            "<div>\n  <p>Text</p>\n  <textarea>line</textarea><b>bold</b>text\n  <pre>code</pre>\n</div>\n<br>\n"
+        );
+    }
+
+    #[test]
+    fn it_should_end_pretty_output_with_newline() {
+        let ast = parse("a Link\n").unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                pretty: Some(HsmlPrettyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(html_content, "<a>Link</a>\n");
+    }
+
+    #[test]
+    fn it_should_not_break_whitespace_sensitive_content() {
+        let input = "section\n  pre\n    code\n    div\n  div\n    textarea.\n      line 1\n        line 2\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                pretty: Some(HsmlPrettyOptions {
+                    indent_width: 4,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            "<section>\n    <pre><code></code><div></div></pre>\n    <div><textarea>line 1\n  line 2</textarea></div>\n</section>\n"
+        );
+    }
+
+    #[test]
+    fn it_should_compile_doctype() {
+        let input = "doctype html\nhtml\n  body\n    br\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            "<!DOCTYPE html><html><body><br></body></html>"
+        );
+    }
+
+    #[test]
+    fn it_should_use_mode_of_doctype() {
+        let input = "doctype strict\nhtml\n  body\n    input(checked)\n";
+
+        let ast = parse(input).unwrap();
+
+        assert_eq!(
+            compile(&ast, &HsmlCompileOptions::default()).unwrap(),
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd"><html><body><input checked="checked" /></body></html>"#
+        );
+        assert_eq!(
+            compile(
+                &ast,
+                &HsmlCompileOptions {
+                    mode_from_doctype: false,
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd"><html><body><input checked></body></html>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_xml_doctype() {
+        let input = "doctype xml\nrss\n  channel\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<?xml version="1.0" encoding="utf-8" ?><rss><channel/></rss>"#
+        );
+    }
+
+    #[test]
+    fn it_should_match_doctype_shortcuts_case_insensitively() {
+        let input = "doctype HTML\nhtml\n  body\n    br\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            "<!DOCTYPE html><html><body><br></body></html>"
+        );
+    }
+
+    #[test]
+    fn it_should_compile_pipe_text() {
+        let input = r#"p Hello
+  strong world
+  |  and more
+  // dev comments do not separate text
+  | on the next line
+  br
+  | a < b
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            "<p>Hello<strong>world</strong> and more\non the next line<br>a &lt; b</p>"
+        );
+    }
+
+    #[test]
+    fn it_should_separate_inline_text_from_pipe_text() {
+        let input = "p first\n  | second\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                pretty: Some(HsmlPrettyOptions::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(html_content, "<p>first\nsecond</p>\n");
+    }
+
+    #[test]
+    fn it_should_compile_interpolated_tags() {
+        let input = r#"p A & #[a.link(href="/?a&b") #[strong home]] page
+p!= <i>raw</i> #[em <b>]
+p.
+  See #[code x < y]
+  and #[br] more
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            concat!(
+                r#"<p>A &amp; <a class="link" href="/?a&amp;b"><strong>home</strong></a> page</p>"#,
+                "<p><i>raw</i> <em>&lt;b&gt;</em></p>",
+                "<p>See <code>x &lt; y</code>\nand <br> more</p>",
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_compile_block_expansion() {
+        let input = "ul\n  li: a(href=\"/\") Home\n  li: a: span.icon\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<ul><li><a href="/">Home</a></li><li><a><span class="icon"></span></a></li></ul>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_raw_html() {
+        let input = r#"<!-- vendor snippet -->
+div
+  <img src="pixel.gif?a=1&b=2">
+  <
+    <script>
+      track("a < b");
+    </script>
+  p Text
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            concat!(
+                "<!-- vendor snippet -->",
+                r#"<div><img src="pixel.gif?a=1&b=2"><script>"#,
+                "\n  track(\"a < b\");\n",
+                "</script><p>Text</p></div>",
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_compile_raw_text_elements() {
+        let input = r##"script.
+  if (a < b && c) {
+    render("#[b]");
+  }
+style a > b { color: red; }
+p a < b
+"##;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            concat!(
+                "<script>if (a < b && c) {\n  render(\"#[b]\");\n}</script>",
+                "<style>a > b { color: red; }</style>",
+                "<p>a &lt; b</p>",
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_compile_raw_text_elements_in_xml() {
+        let input = "script if (a < b) {}\nstyle.\nscript!= if (a < b) {}\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(
+            &ast,
+            &HsmlCompileOptions {
+                mode: HsmlOutputMode::Xml,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            html_content,
+            concat!(
+                "<script><![CDATA[if (a < b) {}]]></script>",
+                "<style></style>",
+                "<script>if (a < b) {}</script>",
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_compile_ids_with_any_characters() {
+        let input = "section#main-content\n  #user_profile.card\n  p#größe Text\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<section id="main-content"><div id="user_profile" class="card"></div><p id="größe">Text</p></section>"#
+        );
+    }
+
+    #[test]
+    fn it_should_end_names_at_closing_bracket_only_in_interpolation() {
+        let input = "div.a]b#c]d\n  p Hello #[b.x#y] world\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<div id="c]d" class="a]b"><p>Hello <b id="y" class="x"></b> world</p></div>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_any_quote_style() {
+        let input = "img(alt='say \"hi\"' width=384 :title=`Hi ${name}`)\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<img alt="say &quot;hi&quot;" width="384" :title="Hi ${name}">"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_decoded_escape_sequences() {
+        let input = r#"p(title="say \"hi\"" alt='it\'s' data-re="\d+") Use \#[b] for \\ and #[b bold]
+"#;
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<p title="say &quot;hi&quot;" alt="it's" data-re="\d+">Use #[b] for \ and <b>bold</b></p>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_multiple_attribute_groups() {
+        let input = "a(href=\"/\").link(target=\"_blank\") Home\n";
+
+        let ast = parse(input).unwrap();
+
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
+
+        assert_eq!(
+            html_content,
+            r#"<a class="link" href="/" target="_blank">Home</a>"#
+        );
+    }
+
+    #[test]
+    fn it_should_compile_multibyte_characters() {
+        let input = r#"//! Grüße 🎉
+div#größe.bg-[ü].日本(title="日本語 \"😀\"" alt='ä' data-x=ö)
+  // this is empty 🤷
+  p Grüße #[em 😀] 日本
+  p.
+    Ünïcödé 👋
+    テキスト
+  | 🤷 pipe
+  span: b 🎉
+"#;
+
+        let ast = parse(input).unwrap();
 
-        let html_content = compile(&ast, &HsmlCompileOptions::default());
+        let html_content = compile(&ast, &HsmlCompileOptions::default()).unwrap();
 
         assert_eq!(
             html_content,
-            r#"<div ref="container" :class="containerClass"><div class="sticky top-0 z10 backdrop-blur native:lg:w-[calc(100vw-5rem)] native:xl:w-[calc(135%+(100vw-1200px)/2)]" pt="[env(safe-area-inset-top,0)]" bg="[rgba(var(--rgb-bg-base),0.7)]"><div class="flex justify-between px5 py2 native:xl:flex" :class="{ 'xl:hidden': $route.name !== 'tag' }" border="b base"><div class="flex gap-3 items-center py2 w-full" :overflow-hidden="!noOverflowHidden ? '' : false"><NuxtLink class="items-center btn-text p-0 xl:hidden" v-if="backOnSmallScreen || back" flex="~ gap1" :aria-label="$t('nav.back')" @click="$router.go(-1)"><div class="rtl-flip" i-ri:arrow-left-line/></NuxtLink><div class="flex w-full native-mac:justify-center native-mac:text-center native-mac:sm:justify-start" :truncate="!noOverflowHidden ? '' : false" data-tauri-drag-region><slot name="title"/></div><div class="sm:hidde nh-7 w-1px"/></div><div class="flex items-center flex-shrink-0 gap-x-2"><slot name="actions"/><PwaBadge class="lg:hidden"/><NavUser v-if="isHydrated"/><NavUserSkeleton v-else/></div></div><slot name="header"><div hidden/></slot></div><PwaInstallPrompt class="lg:hidden"/><div class="m-auto" :class="isHydrated && wideLayout ? 'xl:w-full sm:max-w-600px' : 'sm:max-w-600px md:shrink-0'"><div class="h-6" hidden :class="{ 'xl:block': $route.name !== 'tag' && !$slots.header }"/><slot/></div></div>"#
+            r#"<!-- Grüße 🎉 --><div id="größe" class="bg-[ü] 日本" title="日本語 &quot;😀&quot;" alt="ä" data-x="ö"><p>Grüße <em>😀</em> 日本</p><p>Ünïcödé 👋
+テキスト</p>🤷 pipe<span><b>🎉</b></span></div>"#
         );
-        assert_eq!(rest, "");
     }
 }
@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::parser::span::Position;
+
+/// An error that occurred while formatting a parsed hsml source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HsmlFormatError {
+    /// Nodes like attributes can only be formatted as part of a tag
+    UnsupportedNode { position: Position },
+}
+
+impl HsmlFormatError {
+    pub fn position(&self) -> Position {
+        match self {
+            HsmlFormatError::UnsupportedNode { position } => *position,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            HsmlFormatError::UnsupportedNode { .. } => {
+                String::from("node can not be formatted at this position")
+            }
+        }
+    }
+}
+
+impl fmt::Display for HsmlFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = self.position();
+
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message(),
+            position.line,
+            position.column
+        )
+    }
+}
+
+impl std::error::Error for HsmlFormatError {}
@@ -0,0 +1,478 @@
+use crate::parser::{
+    HsmlNode, RootNode, attribute::node::AttributeNode, comment::node::CommentNode,
+    raw_html::node::RawHtmlNode, span::Span, tag::node::TagNode, text::node::TextNode,
+};
+
+use self::error::HsmlFormatError;
+
+pub mod error;
+
+pub struct HsmlFormatOptions {
+    /// The string used for one level of indentation
+    pub indent_string: String,
+    /// Attribute lists that would make a line longer than this are wrapped, one attribute per line
+    pub print_width: usize,
+}
+
+impl Default for HsmlFormatOptions {
+    fn default() -> Self {
+        Self {
+            indent_string: String::from("  "),
+            print_width: 80,
+        }
+    }
+}
+
+fn format_comment_node(comment_node: &CommentNode) -> String {
+    let mut hsml_content = String::new();
+
+    hsml_content.push_str(if comment_node.is_dev { "//" } else { "//!" });
+    hsml_content.push_str(&comment_node.text);
+
+    hsml_content
+}
+
+fn format_attribute_node(attribute_node: &AttributeNode) -> String {
+    let mut hsml_content = String::new();
+
+    hsml_content.push_str(&attribute_node.key);
+
+    if let Some(value) = &attribute_node.raw_value {
+        if attribute_node.is_unescaped {
+            hsml_content.push('!');
+        }
+
+        let quote = attribute_node.quote.map_or("\"", |quote| quote.as_str());
+
+        hsml_content.push('=');
+        hsml_content.push_str(quote);
+        hsml_content.push_str(value);
+        hsml_content.push_str(quote);
+    }
+
+    hsml_content
+}
+
+/// Format the attribute list on a single line if possible, otherwise one attribute per line.
+///
+/// A dev comment that started on the line of the preceding attribute stays on that line.
+fn format_attribute_nodes(
+    attribute_nodes: &[HsmlNode],
+    line_prefix: &str,
+    level: usize,
+    options: &HsmlFormatOptions,
+) -> String {
+    let attributes: Vec<String> = attribute_nodes
+        .iter()
+        .filter_map(|node| match node {
+            HsmlNode::Attribute(attribute_node) => Some(format_attribute_node(attribute_node)),
+            _ => None,
+        })
+        .collect();
+
+    let has_comments = attributes.len() != attribute_nodes.len();
+    let has_multiline_values = attributes.iter().any(|attribute| attribute.contains('\n'));
+
+    let single_line = format!("({})", attributes.join(" "));
+    let fits = line_prefix.chars().count() + single_line.chars().count() <= options.print_width;
+
+    if !has_comments && !has_multiline_values && fits {
+        return single_line;
+    }
+
+    let attribute_indent = options.indent_string.repeat(level + 1);
+
+    let mut hsml_content = String::from("(");
+    let mut previous_span: Option<Span> = None;
+
+    for node in attribute_nodes {
+        match node {
+            HsmlNode::Attribute(attribute_node) => {
+                hsml_content.push('\n');
+                hsml_content.push_str(&attribute_indent);
+                hsml_content.push_str(&format_attribute_node(attribute_node));
+            }
+            HsmlNode::Comment(comment_node) => {
+                let is_trailing =
+                    previous_span.is_some_and(|span| span.end.line == comment_node.span.start.line);
+
+                if is_trailing {
+                    hsml_content.push(' ');
+                } else {
+                    hsml_content.push('\n');
+                    hsml_content.push_str(&attribute_indent);
+                }
+
+                hsml_content.push_str(&format_comment_node(comment_node));
+            }
+            _ => {}
+        }
+
+        previous_span = match node {
+            HsmlNode::Attribute(_) => node.span(),
+            _ => None,
+        };
+    }
+
+    hsml_content.push('\n');
+    hsml_content.push_str(&options.indent_string.repeat(level));
+    hsml_content.push(')');
+
+    hsml_content
+}
+
+/// Inline text stays behind the tag, text spanning multiple lines becomes a text block.
+///
+/// Empty text keeps the bare `.` of an empty text block, because inline text can not be empty.
+fn format_text_node(text_node: &TextNode, level: usize, options: &HsmlFormatOptions) -> String {
+    let mut hsml_content = String::new();
+
+    if text_node.is_unescaped {
+        hsml_content.push_str("!=");
+    }
+
+    if text_node.raw_text.is_empty() {
+        hsml_content.push('.');
+
+        return hsml_content;
+    }
+
+    if !text_node.raw_text.contains('\n') {
+        hsml_content.push(' ');
+        hsml_content.push_str(&text_node.raw_text);
+
+        return hsml_content;
+    }
+
+    let indent = options.indent_string.repeat(level + 1);
+
+    hsml_content.push('.');
+
+    for line in text_node.raw_text.split('\n') {
+        hsml_content.push('\n');
+
+        if !line.trim().is_empty() {
+            hsml_content.push_str(&indent);
+            hsml_content.push_str(line);
+        }
+    }
+
+    hsml_content
+}
+
+/// Single line HTML starting with `<` stays on its line, everything else becomes a `<` block.
+fn format_raw_html_node(
+    raw_html_node: &RawHtmlNode,
+    level: usize,
+    options: &HsmlFormatOptions,
+) -> String {
+    let indent = options.indent_string.repeat(level);
+
+    if !raw_html_node.html.contains('\n') && raw_html_node.html.starts_with('<') {
+        return format!("{indent}{}\n", raw_html_node.html);
+    }
+
+    let mut hsml_content = format!("{indent}<\n");
+
+    for line in raw_html_node.html.lines() {
+        if !line.is_empty() {
+            hsml_content.push_str(&indent);
+            hsml_content.push_str(&options.indent_string);
+            hsml_content.push_str(line);
+        }
+
+        hsml_content.push('\n');
+    }
+
+    hsml_content
+}
+
+fn format_tag_node(
+    tag_node: &TagNode,
+    mut hsml_content: String,
+    level: usize,
+    options: &HsmlFormatOptions,
+) -> Result<String, HsmlFormatError> {
+    // `div` is implied by an id or class
+    let is_implicit_div =
+        tag_node.tag == "div" && (tag_node.id.is_some() || tag_node.classes.is_some());
+    if !is_implicit_div {
+        hsml_content.push_str(&tag_node.tag);
+    }
+
+    if let Some(id_node) = &tag_node.id {
+        hsml_content.push('#');
+        hsml_content.push_str(&id_node.id);
+    }
+
+    if let Some(class_nodes) = &tag_node.classes {
+        for class_node in class_nodes {
+            hsml_content.push('.');
+            hsml_content.push_str(&class_node.name);
+        }
+    }
+
+    if let Some(attribute_nodes) = &tag_node.attributes
+        && !attribute_nodes.is_empty()
+    {
+        let attributes = format_attribute_nodes(attribute_nodes, &hsml_content, level, options);
+        hsml_content.push_str(&attributes);
+    }
+
+    if let Some(text_node) = &tag_node.text {
+        hsml_content.push_str(&format_text_node(text_node, level, options));
+    }
+
+    if tag_node.is_block_expansion
+        && let Some([HsmlNode::Tag(child_node)]) = tag_node.children.as_deref()
+    {
+        hsml_content.push_str(": ");
+
+        return format_tag_node(child_node, hsml_content, level, options);
+    }
+
+    hsml_content.push('\n');
+
+    if let Some(child_nodes) = &tag_node.children {
+        hsml_content.push_str(&format_nodes(child_nodes, level + 1, options)?);
+    }
+
+    Ok(hsml_content)
+}
+
+/// Format sibling nodes, multiple blank lines between them are collapsed into one.
+fn format_nodes(
+    nodes: &[HsmlNode],
+    level: usize,
+    options: &HsmlFormatOptions,
+) -> Result<String, HsmlFormatError> {
+    let mut hsml_content = String::new();
+    let mut previous_span: Option<Span> = None;
+
+    for node in nodes {
+        let span = node.span();
+
+        if let (Some(previous_span), Some(span)) = (previous_span, span)
+            && span.start.line > previous_span.end.line + 1
+        {
+            hsml_content.push('\n');
+        }
+
+        match node {
+            HsmlNode::Doctype(doctype_node) => {
+                hsml_content.push_str("doctype ");
+                hsml_content.push_str(&doctype_node.value);
+                hsml_content.push('\n');
+            }
+            HsmlNode::Tag(tag_node) => hsml_content.push_str(&format_tag_node(
+                tag_node,
+                options.indent_string.repeat(level),
+                level,
+                options,
+            )?),
+            HsmlNode::Comment(comment_node) => {
+                hsml_content.push_str(&options.indent_string.repeat(level));
+                hsml_content.push_str(&format_comment_node(comment_node));
+                hsml_content.push('\n');
+            }
+            HsmlNode::Text(text_node) => {
+                hsml_content.push_str(&options.indent_string.repeat(level));
+                hsml_content.push('|');
+
+                if !text_node.raw_text.is_empty() {
+                    hsml_content.push(' ');
+                    hsml_content.push_str(&text_node.raw_text);
+                }
+
+                hsml_content.push('\n');
+            }
+            HsmlNode::RawHtml(raw_html_node) => {
+                hsml_content.push_str(&format_raw_html_node(raw_html_node, level, options));
+            }
+            _ => {
+                return Err(HsmlFormatError::UnsupportedNode {
+                    position: span.map(|span| span.start).unwrap_or_default(),
+                });
+            }
+        }
+
+        previous_span = span;
+    }
+
+    Ok(hsml_content)
+}
+
+pub fn format(hsml_ast: &RootNode, options: &HsmlFormatOptions) -> Result<String, HsmlFormatError> {
+    format_nodes(&hsml_ast.nodes, 0, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        formatter::{HsmlFormatOptions, error::HsmlFormatError, format},
+        parser::{
+            HsmlNode, RootNode,
+            class::node::ClassNode,
+            parse::parse,
+            span::{Position, Span},
+        },
+    };
+
+    fn format_source(input: &str) -> String {
+        format(&parse(input).unwrap(), &HsmlFormatOptions::default()).unwrap()
+    }
+
+    const CORPUS: &[&str] = &[
+        include_str!("../../example.hsml"),
+        include_str!("../../example2.hsml"),
+        "h1.text-red.uppercase Hello World\n",
+        "doctype html\n\nhtml\n  body\n",
+        "p Hello\n  strong world\n  |  and more\n  |\n  | end\n",
+        "div\n  h1.text-red Hello\n\n\n\n  p(id=\"a\" disabled) World\n",
+        "div\n  p.lead!= <b>raw</b>\n  a(href!=\"a&b\")!=.\n    <i>first</i>\n    <i>second</i>\n",
+        "div\n\t// dev comment\n\t//! native comment\n\tp.\n\t\tfirst line\n\n\t\tsecond line\n",
+        "ul\n  li.active: a(href=\"/\") Home\n  li: a: span.icon\n    | nested\n",
+        "img(alt='say \"hi\"' width=384 :title=`Hi ${name}` hidden)\n",
+        "p(title=\"say \\\"hi\\\"\" alt='it\\'s') Use \\#[b] for \\\\ and #[b bold]\n",
+        "<!-- vendor -->\ndiv\n  <img src=\"pixel.gif\">\n  <\n    <script>\n      track();\n\n    </script>\n  p Text\n",
+        "script.\n  if (a) {\n    b();\n  }\nstyle a { color: red; }\n",
+        "p Hello #[strong.red(title=\"x\") world]!\n  | and #[em more]\n  p.\n    #[b first]\n    second\n",
+        r#"img(
+  // leading comment
+  src="/avatar.jpg" // trailing comment
+  alt=""
+)
+"#,
+        r#"button(
+  class="{
+    'active': isActive,
+  }"
+  @click="toggle"
+) Toggle
+"#,
+        "#größe.bg-[ü].日本(title=\"日本語 😀\" alt='ä')\n  // this is empty 🤷\n  p Grüße #[em 😀] 日本\n  p.\n    Ünïcödé 👋\n    テキスト\n  | 🤷 pipe\n",
+        "p.\np!=.\n",
+        "div\r\n  p Hello\r\n  span\r\n",
+        "div\r\n  // c\r\n  p.\r\n    a\r\n    b\r\n",
+        r#"a(href="https://example.com/a/very/long/path/to/some/resource" target="_blank" rel="noopener noreferrer") Link
+"#,
+    ];
+
+    #[test]
+    fn it_should_be_idempotent() {
+        for input in CORPUS {
+            let formatted = format_source(input);
+
+            assert_eq!(format_source(&formatted), formatted, "input:\n{input}");
+        }
+    }
+
+    #[test]
+    fn it_should_keep_formatted_sources() {
+        assert_eq!(format_source(CORPUS[2]), CORPUS[2]);
+        assert_eq!(format_source(CORPUS[3]), CORPUS[3]);
+        assert_eq!(format_source(CORPUS[4]), CORPUS[4]);
+        assert_eq!(format_source(CORPUS[6]), CORPUS[6]);
+        assert_eq!(format_source(CORPUS[8]), CORPUS[8]);
+        assert_eq!(format_source(CORPUS[9]), CORPUS[9]);
+        assert_eq!(format_source(CORPUS[10]), CORPUS[10]);
+        assert_eq!(format_source(CORPUS[11]), CORPUS[11]);
+        assert_eq!(format_source(CORPUS[12]), CORPUS[12]);
+        assert_eq!(format_source(CORPUS[13]), CORPUS[13]);
+        assert_eq!(format_source(CORPUS[14]), CORPUS[14]);
+        assert_eq!(format_source(CORPUS[15]), CORPUS[15]);
+        assert_eq!(format_source(CORPUS[16]), CORPUS[16]);
+        assert_eq!(format_source(CORPUS[17]), CORPUS[17]);
+    }
+
+    #[test]
+    fn it_should_normalize_indentation() {
+        let formatted = format_source("div\n    p\n        span Hello\n    p.\n        World\n");
+
+        assert_eq!(formatted, "div\n  p\n    span Hello\n  p World\n");
+    }
+
+    #[test]
+    fn it_should_normalize_crlf_line_endings() {
+        assert_eq!(format_source(CORPUS[18]), "div\n  p Hello\n  span\n");
+        assert_eq!(
+            format_source(CORPUS[19]),
+            "div\n  // c\n  p.\n    a\n    b\n"
+        );
+    }
+
+    #[test]
+    fn it_should_normalize_order_and_implicit_div() {
+        let formatted = format_source("div#main.a.b(hidden) Text\ndiv(hidden)\n");
+
+        assert_eq!(formatted, "#main.a.b(hidden) Text\ndiv(hidden)\n");
+    }
+
+    #[test]
+    fn it_should_collapse_blank_lines() {
+        let formatted = format_source("h1 Title\n\n\n\np Text\np Text\n");
+
+        assert_eq!(formatted, "h1 Title\n\np Text\np Text\n");
+    }
+
+    #[test]
+    fn it_should_merge_attribute_groups() {
+        let formatted = format_source("a(href=\"/\").link(target=\"_blank\")#home Home\n");
+
+        assert_eq!(
+            formatted,
+            "a#home.link(href=\"/\" target=\"_blank\") Home\n"
+        );
+    }
+
+    #[test]
+    fn it_should_join_attributes() {
+        let formatted = format_source("img(\n  src=\"a.png\"\n  , alt=\"\"\n)\n");
+
+        assert_eq!(formatted, "img(src=\"a.png\" alt=\"\")\n");
+    }
+
+    #[test]
+    fn it_should_wrap_long_attribute_lists() {
+        let formatted = format_source(CORPUS[20]);
+
+        assert_eq!(
+            formatted,
+            r#"a(
+  href="https://example.com/a/very/long/path/to/some/resource"
+  target="_blank"
+  rel="noopener noreferrer"
+) Link
+"#
+        );
+    }
+
+    #[test]
+    fn it_should_keep_comments() {
+        let formatted = format_source(include_str!("../../example2.hsml"));
+
+        assert!(formatted.starts_with("//! test comment on root layer\n"));
+        assert!(formatted.contains("\n  //! test comment\n"));
+        assert!(formatted.contains("\n    // supports attribute inline comments\n"));
+        assert!(formatted.contains("\n    alt=\"\" // this is empty 🤷\n"));
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_format_unsupported_node() {
+        let ast = RootNode {
+            nodes: vec![HsmlNode::Class(ClassNode {
+                name: String::from("lead"),
+                span: Span::new(Position::new(1, 1, 2), Position::new(6, 1, 7)),
+            })],
+        };
+
+        assert_eq!(
+            format(&ast, &HsmlFormatOptions::default()),
+            Err(HsmlFormatError::UnsupportedNode {
+                position: Position::new(1, 1, 2),
+            })
+        );
+    }
+}
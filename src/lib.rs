@@ -1,11 +1,112 @@
 pub mod compiler;
+pub mod formatter;
+pub mod linter;
 pub mod parser;
+pub mod wasm;
 
-use wasm_bindgen::prelude::*;
+pub use wasm::compile_content;
 
-#[wasm_bindgen]
-pub fn compile_content(source: &str) -> String {
-    let (_, ast) = parser::parse::parse(source).unwrap();
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compiler::{HsmlCompileOptions, HsmlOutputMode, compile},
+        formatter::{HsmlFormatOptions, format},
+        linter::{lint, rules::builtin_rules},
+        parser::{json::to_json, parse::parse},
+    };
 
-    compiler::compile(&ast, &compiler::HsmlCompileOptions::default())
+    const SOURCES: &[&str] = &[
+        include_str!("../example.hsml"),
+        include_str!("../example2.hsml"),
+        "doctype html\nhtml\n  body\n    p Hello #[strong world]!\n    | and more\n",
+        "div\n  <\n    <b>raw</b>\n  script.\n    if (a) {}\n  a(href='/' :x=`y` z=1)!= <i>x</i>\n",
+        "ul\n  li.active: a(href=\"/\") Home\n  p.größe(title=\"日本語 😀\") Grüße 👋\n",
+    ];
+
+    /// Characters that have a meaning in hsml, so mutations hit the interesting paths of the scanners.
+    const ALPHABET: &[&str] = &[
+        "(", ")", "[", "]", "\"", "'", "`", "\\", "#", ".", ":", ": ", "=", "!=", ",", "|", "//",
+        "//!", "<", "#[", " ", "  ", "\t", "\u{a0}", "\u{3000}", "\n", "\r\n", "\r", "a", "ü",
+        "😀",
+    ];
+
+    /// Deterministic xorshift, so failures can be reproduced.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, max: usize) -> usize {
+            (self.next() % max as u64) as usize
+        }
+    }
+
+    fn char_boundary(source: &str, index: usize) -> usize {
+        (0..=index)
+            .rev()
+            .find(|&index| source.is_char_boundary(index))
+            .unwrap_or(0)
+    }
+
+    fn mutate(source: &str, rng: &mut XorShift) -> String {
+        let mut source = String::from(source);
+
+        for _ in 0..=rng.below(4) {
+            let index = char_boundary(&source, rng.below(source.len() + 1));
+
+            match rng.below(3) {
+                0 => source.insert_str(index, ALPHABET[rng.below(ALPHABET.len())]),
+                1 => {
+                    let end = char_boundary(&source, index + rng.below(8)).max(index);
+                    source.replace_range(index..end, "");
+                }
+                _ => source.truncate(index),
+            }
+        }
+
+        source
+    }
+
+    /// Run the source through every entry point, returns the formatted source if it could be parsed.
+    fn run_entry_points(source: &str) -> Option<String> {
+        let ast = parse(source).ok()?;
+
+        for mode in [
+            HsmlOutputMode::Html5,
+            HsmlOutputMode::Xhtml,
+            HsmlOutputMode::Xml,
+        ] {
+            let _ = compile(
+                &ast,
+                &HsmlCompileOptions {
+                    mode,
+                    ..Default::default()
+                },
+            );
+        }
+
+        lint(&ast, &builtin_rules());
+        to_json(&ast, false).unwrap();
+
+        format(&ast, &HsmlFormatOptions::default()).ok()
+    }
+
+    #[test]
+    fn it_should_not_panic_on_mutated_sources() {
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..2000 {
+            let source = mutate(SOURCES[rng.below(SOURCES.len())], &mut rng);
+
+            // the formatted source goes through every entry point again
+            if let Some(formatted) = run_entry_points(&source) {
+                run_entry_points(&formatted);
+            }
+        }
+    }
 }
@@ -0,0 +1,153 @@
+use serde::Serialize;
+
+use crate::parser::{HsmlNode, RootNode, span::Span};
+
+pub mod rules;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    /// Name of the rule that reported the diagnostic
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+}
+
+/// A lint rule, it gets called for every node of the tree.
+pub trait Rule {
+    /// Unique kebab-case name of the rule, e.g. `img-alt`.
+    fn name(&self) -> &'static str;
+
+    fn severity(&self) -> Severity;
+
+    fn visit(&self, node: &HsmlNode, context: &mut LintContext);
+}
+
+/// Collects the diagnostics a [`Rule`] reports while visiting a node.
+pub struct LintContext<'a> {
+    rule: &'static str,
+    severity: Severity,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl LintContext<'_> {
+    pub fn report(&mut self, message: impl Into<String>, span: Span) {
+        self.diagnostics.push(Diagnostic {
+            rule: self.rule,
+            severity: self.severity,
+            message: message.into(),
+            span,
+        });
+    }
+}
+
+fn visit_node(node: &HsmlNode, rules: &[Box<dyn Rule>], diagnostics: &mut Vec<Diagnostic>) {
+    for rule in rules {
+        let mut context = LintContext {
+            rule: rule.name(),
+            severity: rule.severity(),
+            diagnostics,
+        };
+
+        rule.visit(node, &mut context);
+    }
+
+    let child_nodes = match node {
+        HsmlNode::Tag(tag_node) => vec![
+            &tag_node.attributes,
+            tag_node
+                .text
+                .as_ref()
+                .map_or(&None, |text_node| &text_node.segments),
+            &tag_node.children,
+        ],
+        HsmlNode::Text(text_node) => vec![&text_node.segments],
+        _ => vec![],
+    };
+
+    // interpolated tags are visited like any other tag
+    for child_node in child_nodes.into_iter().flatten().flatten() {
+        visit_node(child_node, rules, diagnostics);
+    }
+}
+
+/// Run given rules over every node, the diagnostics are ordered by their position in the source.
+pub fn lint(hsml_ast: &RootNode, rules: &[Box<dyn Rule>]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for node in &hsml_ast.nodes {
+        visit_node(node, rules, &mut diagnostics);
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.start.offset);
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        linter::{LintContext, Rule, Severity, lint},
+        parser::{HsmlNode, parse::parse},
+    };
+
+    struct NoComments;
+
+    impl Rule for NoComments {
+        fn name(&self) -> &'static str {
+            "no-comments"
+        }
+
+        fn severity(&self) -> Severity {
+            Severity::Info
+        }
+
+        fn visit(&self, node: &HsmlNode, context: &mut LintContext) {
+            if let HsmlNode::Comment(comment_node) = node {
+                context.report("comment", comment_node.span);
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_visit_every_node() {
+        let ast = parse("// a\ndiv(\n  // b\n  id=\"x\"\n)\n  // c\n").unwrap();
+
+        let diagnostics = lint(&ast, &[Box::new(NoComments)]);
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (
+                    diagnostic.rule,
+                    diagnostic.severity,
+                    diagnostic.span.start.line
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("no-comments", Severity::Info, 1),
+                ("no-comments", Severity::Info, 3),
+                ("no-comments", Severity::Info, 6),
+            ]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use crate::{
+    linter::{LintContext, Rule, Severity},
+    parser::HsmlNode,
+};
+
+/// An attribute must not be set more than once on the same tag.
+pub struct DuplicateAttribute;
+
+impl Rule for DuplicateAttribute {
+    fn name(&self) -> &'static str {
+        "duplicate-attribute"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn visit(&self, node: &HsmlNode, context: &mut LintContext) {
+        let HsmlNode::Tag(tag_node) = node else {
+            return;
+        };
+
+        let mut keys = HashSet::new();
+
+        for attribute in tag_node.attributes.iter().flatten() {
+            if let HsmlNode::Attribute(attribute_node) = attribute
+                && !keys.insert(attribute_node.key.as_str())
+            {
+                context.report(
+                    format!("duplicate attribute `{}`", attribute_node.key),
+                    attribute_node.span,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        linter::{lint, rules::duplicate_attribute::DuplicateAttribute},
+        parser::{
+            parse::parse,
+            span::{Position, Span},
+        },
+    };
+
+    #[test]
+    fn it_should_report_duplicate_attribute() {
+        let ast = parse("input(type=\"text\" value=\"\" type=\"email\")\n").unwrap();
+
+        let diagnostics = lint(&ast, &[Box::new(DuplicateAttribute)]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "duplicate attribute `type`");
+        assert_eq!(
+            diagnostics[0].span,
+            Span::new(Position::new(27, 1, 28), Position::new(39, 1, 40))
+        );
+    }
+
+    #[test]
+    fn it_should_report_duplicate_attribute_across_groups() {
+        let ast = parse("a(href=\"/\").link(href=\"/home\") Home\n").unwrap();
+
+        let diagnostics = lint(&ast, &[Box::new(DuplicateAttribute)]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "duplicate attribute `href`");
+        assert_eq!(diagnostics[0].span.start.column, 18);
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_report_same_attribute_on_different_tags() {
+        let ast = parse("input(type=\"text\")\ninput(type=\"text\")\n").unwrap();
+
+        assert!(lint(&ast, &[Box::new(DuplicateAttribute)]).is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use crate::{
+    linter::{LintContext, Rule, Severity},
+    parser::HsmlNode,
+};
+
+/// Listing the same class twice has no effect and is most likely a mistake.
+pub struct DuplicateClass;
+
+impl Rule for DuplicateClass {
+    fn name(&self) -> &'static str {
+        "duplicate-class"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn visit(&self, node: &HsmlNode, context: &mut LintContext) {
+        let HsmlNode::Tag(tag_node) = node else {
+            return;
+        };
+
+        let mut names = HashSet::new();
+
+        for class_node in tag_node.classes.iter().flatten() {
+            if !names.insert(class_node.name.as_str()) {
+                context.report(
+                    format!("duplicate class `{}`", class_node.name),
+                    class_node.span,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        linter::{lint, rules::duplicate_class::DuplicateClass},
+        parser::{
+            parse::parse,
+            span::{Position, Span},
+        },
+    };
+
+    #[test]
+    fn it_should_report_duplicate_class() {
+        let ast = parse("div.a.b.a\n").unwrap();
+
+        let diagnostics = lint(&ast, &[Box::new(DuplicateClass)]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "duplicate class `a`");
+        assert_eq!(
+            diagnostics[0].span,
+            Span::new(Position::new(7, 1, 8), Position::new(9, 1, 10))
+        );
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_report_distinct_classes() {
+        let ast = parse("div.a.b\n  div.a.b\n").unwrap();
+
+        assert!(lint(&ast, &[Box::new(DuplicateClass)]).is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{
+    linter::{LintContext, Rule, Severity},
+    parser::HsmlNode,
+};
+
+/// Images need an `alt` attribute for screen readers, it may be empty for decorative images.
+pub struct ImgAlt;
+
+impl Rule for ImgAlt {
+    fn name(&self) -> &'static str {
+        "img-alt"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn visit(&self, node: &HsmlNode, context: &mut LintContext) {
+        let HsmlNode::Tag(tag_node) = node else {
+            return;
+        };
+
+        if tag_node.tag != "img" {
+            return;
+        }
+
+        // bound attributes like `:alt` also provide it
+        let has_alt = tag_node.attributes.iter().flatten().any(|node| {
+            matches!(node, HsmlNode::Attribute(attribute_node)
+                if matches!(attribute_node.key.as_str(), "alt" | ":alt" | "v-bind:alt"))
+        });
+
+        if !has_alt {
+            context.report("`img` is missing an `alt` attribute", tag_node.span);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        linter::{lint, rules::img_alt::ImgAlt},
+        parser::parse::parse,
+    };
+
+    #[test]
+    fn it_should_report_missing_alt() {
+        let ast = parse("div\n  img(src=\"a.png\")\n").unwrap();
+
+        let diagnostics = lint(&ast, &[Box::new(ImgAlt)]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "`img` is missing an `alt` attribute"
+        );
+        assert_eq!(diagnostics[0].span.start.line, 2);
+    }
+
+    #[test]
+    fn it_should_report_missing_alt_of_interpolated_img() {
+        let ast = parse("p See #[img(src=\"a.png\")] here\n").unwrap();
+
+        let diagnostics = lint(&ast, &[Box::new(ImgAlt)]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span.start.column, 7);
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_accept_empty_and_bound_alt() {
+        let ast = parse("img(src=\"a.png\" alt=\"\")\nimg(:src=\"url\" :alt=\"name\")\n").unwrap();
+
+        assert!(lint(&ast, &[Box::new(ImgAlt)]).is_empty());
+    }
+}
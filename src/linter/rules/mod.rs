@@ -0,0 +1,14 @@
+use crate::linter::Rule;
+
+pub mod duplicate_attribute;
+pub mod duplicate_class;
+pub mod img_alt;
+
+/// All rules `hsml check` runs.
+pub fn builtin_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(duplicate_attribute::DuplicateAttribute),
+        Box::new(duplicate_class::DuplicateClass),
+        Box::new(img_alt::ImgAlt),
+    ]
+}
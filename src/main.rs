@@ -7,7 +7,7 @@ use cli::{
     exec_parse::exec_parse,
 };
 
-fn main() -> Result<(), &'static str> {
+fn main() -> Result<(), String> {
     let matches = cli().get_matches();
 
     let result = match matches.subcommand() {
@@ -1,38 +1,117 @@
-use nom::{
-    IResult,
-    bytes::complete::{tag, take_till},
+use nom::bytes::complete::{tag, take_till};
+use serde::Serialize;
+
+use crate::parser::{
+    HsmlNode, HsmlProcessContext,
+    comment::node::comment_dev_node,
+    error::{HsmlError, HsmlErrorKind, HsmlResult},
+    span::Span,
 };
 
-use crate::parser::{HsmlNode, HsmlProcessContext, comment::node::comment_dev_node};
+use super::process::{decode_attribute_value, process_attribute, process_attribute_key};
+
+/// How an attribute value was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AttributeQuote {
+    /// `key="value"`
+    Double,
+    /// `key='value'`
+    Single,
+    /// ``key=`value` ``, mostly used for JavaScript template literals in bindings
+    Backtick,
+    /// `key=value`
+    Unquoted,
+}
 
-use super::process::process_attribute;
+impl AttributeQuote {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttributeQuote::Double => "\"",
+            AttributeQuote::Single => "'",
+            AttributeQuote::Backtick => "`",
+            AttributeQuote::Unquoted => "",
+        }
+    }
+}
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename = "attribute", rename_all = "camelCase")]
 pub struct AttributeNode {
     pub key: String,
+    /// The value with decoded escape sequences
     pub value: Option<String>,
+    /// The value as written between the quotes
+    pub raw_value: Option<String>,
+    /// Only set if there is a value
+    pub quote: Option<AttributeQuote>,
+    /// Written as `key!="value"`, the value is emitted without escaping
+    pub is_unescaped: bool,
+    pub span: Span,
 }
 
 pub fn attribute_node<'a>(
     input: &'a str,
     context: &mut HsmlProcessContext,
-) -> IResult<&'a str, AttributeNode> {
-    let (input, attribute) = process_attribute(input, context)?;
+) -> HsmlResult<'a, AttributeNode> {
+    let (rest, attribute) = process_attribute(input, context).map_err(|err| match err {
+        // the value starts with a quote but process_attribute_value could not find the closing one
+        nom::Err::Error(e) | nom::Err::Failure(e) if e.input.starts_with(['"', '\'', '`']) => {
+            let quote = e.input.chars().next().unwrap_or('"');
+            nom::Err::Failure(HsmlError::new(
+                e.input,
+                HsmlErrorKind::UnterminatedQuote(quote),
+            ))
+        }
+        err => nom::Err::convert(err),
+    })?;
 
-    let equal_sign_index = attribute.find('=').unwrap_or(attribute.len());
-    let (key, value) = attribute.split_at(equal_sign_index);
+    // the key itself can contain an `=` inside of brackets, so it is scanned again instead of split at the first `=`
+    let (_, key) = process_attribute_key(input).map_err(nom::Err::convert)?;
+    let value = &attribute[key.len()..];
 
-    // Remove surrounding quotes and leading `=` from value
-    let value = value
-        .strip_prefix(r#"=""#)
-        .and_then(|v| v.strip_suffix('"'))
-        .map(|v| v.to_string());
+    // `key!="value"` opts out of escaping the value
+    let (key, is_unescaped) = match key.strip_suffix('!') {
+        Some(key) if !value.is_empty() => (key, true),
+        _ => (key, false),
+    };
+
+    // Remove leading `=` and surrounding quotes from value
+    let (raw_value, quote) = match value.strip_prefix('=') {
+        Some(value) => {
+            let quote = match value.chars().next() {
+                Some('"') => AttributeQuote::Double,
+                Some('\'') => AttributeQuote::Single,
+                Some('`') => AttributeQuote::Backtick,
+                _ => AttributeQuote::Unquoted,
+            };
+
+            let value = match quote {
+                AttributeQuote::Unquoted => value,
+                _ => &value[1..value.len() - 1],
+            };
+
+            (Some(value), Some(quote))
+        }
+        None => (None, None),
+    };
+
+    // unquoted values can not contain escaped quotes
+    let quote_char = quote.and_then(|quote| quote.as_str().chars().next());
+    let value = raw_value.map(|raw_value| match quote_char {
+        Some(quote_char) => decode_attribute_value(raw_value, quote_char),
+        None => raw_value.to_string(),
+    });
 
     Ok((
-        input,
+        rest,
         AttributeNode {
             key: key.to_string(),
             value,
+            raw_value: raw_value.map(String::from),
+            quote,
+            is_unescaped,
+            span: context.span_between(input, rest),
         },
     ))
 }
@@ -40,7 +119,15 @@ pub fn attribute_node<'a>(
 pub fn attribute_nodes<'a>(
     input: &'a str,
     context: &mut HsmlProcessContext,
-) -> IResult<&'a str, Vec<HsmlNode>> {
+) -> HsmlResult<'a, Vec<HsmlNode>> {
+    let attributes_start = input;
+    let unclosed_attribute_list = || {
+        nom::Err::Failure(HsmlError::new(
+            attributes_start,
+            HsmlErrorKind::UnclosedAttributeList,
+        ))
+    };
+
     let (mut input, _) = tag("(")(input)?;
 
     let mut nodes: Vec<HsmlNode> = vec![];
@@ -57,9 +144,13 @@ pub fn attribute_nodes<'a>(
             break;
         }
 
+        if remaining.is_empty() {
+            return Err(unclosed_attribute_list());
+        }
+
         // if remaining starts with `//`, it is a dev comment
         if remaining.starts_with("//") {
-            let (remaining, comment) = comment_dev_node(remaining)?;
+            let (remaining, comment) = comment_dev_node(remaining, context)?;
             nodes.push(HsmlNode::Comment(comment));
 
             input = remaining;
@@ -81,21 +172,28 @@ pub fn attribute_nodes<'a>(
 mod tests {
     use crate::parser::{
         HsmlNode, HsmlProcessContext,
-        attribute::node::{AttributeNode, attribute_node, attribute_nodes},
+        attribute::node::{AttributeNode, AttributeQuote, attribute_node, attribute_nodes},
         comment::node::CommentNode,
+        error::{HsmlError, HsmlErrorKind},
+        span::{Position, Span},
     };
 
     #[test]
     fn it_should_return_attribute_node() {
-        let mut context = HsmlProcessContext::default();
+        let input = r#"key="value""#;
+        let mut context = HsmlProcessContext::new(input);
 
-        let (input, attribute) = attribute_node(r#"key="value""#, &mut context).unwrap();
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
 
         assert_eq!(
             attribute,
             AttributeNode {
                 key: String::from("key"),
-                value: Some(String::from("value"))
+                value: Some(String::from("value")),
+                raw_value: Some(String::from("value")),
+                quote: Some(AttributeQuote::Double),
+                is_unescaped: false,
+                span: Span::new(Position::new(0, 1, 1), Position::new(11, 1, 12)),
             }
         );
 
@@ -103,18 +201,90 @@ mod tests {
     }
 
     #[test]
-    fn it_should_return_attribute_node_with_multiline() {
-        let mut context = HsmlProcessContext::default();
+    fn it_should_return_unescaped_attribute_node() {
+        let input = r#"href!="a&b""#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
+
+        assert_eq!(
+            attribute,
+            AttributeNode {
+                key: String::from("href"),
+                value: Some(String::from("a&b")),
+                raw_value: Some(String::from("a&b")),
+                quote: Some(AttributeQuote::Double),
+                is_unescaped: true,
+                span: Span::new(Position::new(0, 1, 1), Position::new(11, 1, 12)),
+            }
+        );
+
+        assert_eq!(input, "");
+    }
+
+    #[test]
+    fn it_should_return_attribute_node_with_equal_sign_in_key() {
+        let input = r#"[a="]="日本語""#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
+
+        assert_eq!(
+            attribute,
+            AttributeNode {
+                key: String::from(r#"[a="]"#),
+                value: Some(String::from("日本語")),
+                raw_value: Some(String::from("日本語")),
+                quote: Some(AttributeQuote::Double),
+                is_unescaped: false,
+                span: Span::new(Position::new(0, 1, 1), Position::new(17, 1, 12)),
+            }
+        );
+
+        assert_eq!(input, "");
+    }
+
+    #[test]
+    fn it_should_return_attribute_nodes_with_any_quote_style() {
+        let input = r#"(alt='say "hi"' width=384 :title=`Hi ${name}`)"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attributes) = attribute_nodes(input, &mut context).unwrap();
+
+        let values: Vec<(&str, Option<&str>, Option<AttributeQuote>)> = attributes
+            .iter()
+            .map(|node| match node {
+                HsmlNode::Attribute(attribute) => (
+                    attribute.key.as_str(),
+                    attribute.value.as_deref(),
+                    attribute.quote,
+                ),
+                _ => panic!("expected an attribute node"),
+            })
+            .collect();
+
+        assert_eq!(
+            values,
+            vec![
+                ("alt", Some(r#"say "hi""#), Some(AttributeQuote::Single)),
+                ("width", Some("384"), Some(AttributeQuote::Unquoted)),
+                (":title", Some("Hi ${name}"), Some(AttributeQuote::Backtick)),
+            ]
+        );
+
+        assert_eq!(input, "");
+    }
 
-        let (input, attribute) = attribute_node(
-            r#"class="{
+    #[test]
+    fn it_should_return_attribute_node_with_multiline() {
+        let input = r#"class="{
         'is-active': isActive,
         'is-disabled': isDisabled,
     }"
-    :key="item.id""#,
-            &mut context,
-        )
-        .unwrap();
+    :key="item.id""#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute) = attribute_node(input, &mut context).unwrap();
 
         assert_eq!(
             attribute,
@@ -125,7 +295,16 @@ mod tests {
         'is-active': isActive,
         'is-disabled': isDisabled,
     }"#
-                ))
+                )),
+                raw_value: Some(String::from(
+                    r#"{
+        'is-active': isActive,
+        'is-disabled': isDisabled,
+    }"#
+                )),
+                quote: Some(AttributeQuote::Double),
+                is_unescaped: false,
+                span: Span::new(Position::new(0, 1, 1), Position::new(81, 4, 7)),
             }
         );
 
@@ -138,21 +317,29 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes() {
-        let mut context = HsmlProcessContext::default();
+        let input = r#"(key="value", :key2="value2")"#;
+        let mut context = HsmlProcessContext::new(input);
 
-        let (input, attribute_nodes) =
-            attribute_nodes(r#"(key="value", :key2="value2")"#, &mut context).unwrap();
+        let (input, attribute_nodes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attribute_nodes,
             vec![
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from("key"),
-                    value: Some(String::from("value"))
+                    value: Some(String::from("value")),
+                    raw_value: Some(String::from("value")),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(1, 1, 2), Position::new(12, 1, 13)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key2"),
-                    value: Some(String::from("value2"))
+                    value: Some(String::from("value2")),
+                    raw_value: Some(String::from("value2")),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(14, 1, 15), Position::new(28, 1, 29)),
                 })
             ]
         );
@@ -162,28 +349,33 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes_with_wrapped() {
-        let mut context = HsmlProcessContext::default();
-
-        let (input, attribute_nodes) = attribute_nodes(
-            r#"(
+        let input = r#"(
     key="value"
     :key2="value2"
 )
-"#,
-            &mut context,
-        )
-        .unwrap();
+"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute_nodes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attribute_nodes,
             vec![
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from("key"),
-                    value: Some(String::from("value"))
+                    value: Some(String::from("value")),
+                    raw_value: Some(String::from("value")),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(6, 2, 5), Position::new(17, 2, 16)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key2"),
-                    value: Some(String::from("value2"))
+                    value: Some(String::from("value2")),
+                    raw_value: Some(String::from("value2")),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(22, 3, 5), Position::new(36, 3, 19)),
                 })
             ]
         );
@@ -193,19 +385,16 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes_with_dev_comments() {
-        let mut context = HsmlProcessContext::default();
-
-        let (input, attribute_nodes) = attribute_nodes(
-            r#"(
+        let input = r#"(
     // comment 1
     key="value"
     // comment 2
     :key2="value2"
 )
-"#,
-            &mut context,
-        )
-        .unwrap();
+"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attribute_nodes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attribute_nodes,
@@ -213,18 +402,28 @@ mod tests {
                 HsmlNode::Comment(CommentNode {
                     is_dev: true,
                     text: String::from(" comment 1"),
+                    span: Span::new(Position::new(6, 2, 5), Position::new(18, 2, 17)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from("key"),
                     value: Some(String::from("value")),
+                    raw_value: Some(String::from("value")),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(23, 3, 5), Position::new(34, 3, 16)),
                 }),
                 HsmlNode::Comment(CommentNode {
                     is_dev: true,
                     text: String::from(" comment 2"),
+                    span: Span::new(Position::new(39, 4, 5), Position::new(51, 4, 17)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key2"),
                     value: Some(String::from("value2")),
+                    raw_value: Some(String::from("value2")),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(56, 5, 5), Position::new(70, 5, 19)),
                 }),
             ]
         );
@@ -234,17 +433,14 @@ mod tests {
 
     #[test]
     fn it_should_return_attribute_nodes_with_multiline() {
-        let mut context = HsmlProcessContext::default();
-
-        let (input, attributes) = attribute_nodes(
-            r#"(class="{
+        let input = r#"(class="{
         'is-active': isActive,
         'is-disabled': isDisabled,
     }"
-    :key="item.id")"#,
-            &mut context,
-        )
-        .unwrap();
+    :key="item.id")"#;
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attributes) = attribute_nodes(input, &mut context).unwrap();
 
         assert_eq!(
             attributes,
@@ -257,14 +453,62 @@ mod tests {
         'is-disabled': isDisabled,
     }"#
                     )),
+                    raw_value: Some(String::from(
+                        r#"{
+        'is-active': isActive,
+        'is-disabled': isDisabled,
+    }"#
+                    )),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(1, 1, 2), Position::new(82, 4, 7)),
                 }),
                 HsmlNode::Attribute(AttributeNode {
                     key: String::from(":key"),
                     value: Some(String::from("item.id")),
+                    raw_value: Some(String::from("item.id")),
+                    quote: Some(AttributeQuote::Double),
+                    is_unescaped: false,
+                    span: Span::new(Position::new(87, 5, 5), Position::new(101, 5, 19)),
                 }),
             ]
         );
 
         assert_eq!(input, "");
     }
+
+    #[test]
+    fn it_should_return_attribute_nodes_at_end_of_input() {
+        let input = "(disabled)";
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, attributes) = attribute_nodes(input, &mut context).unwrap();
+
+        assert_eq!(
+            attributes,
+            vec![HsmlNode::Attribute(AttributeNode {
+                key: String::from("disabled"),
+                value: None,
+                raw_value: None,
+                quote: None,
+                is_unescaped: false,
+                span: Span::new(Position::new(1, 1, 2), Position::new(9, 1, 10)),
+            })]
+        );
+
+        assert_eq!(input, "");
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_return_unclosed_attribute_nodes_at_end_of_input() {
+        let input = "(disabled // comment";
+        let mut context = HsmlProcessContext::new(input);
+
+        assert_eq!(
+            attribute_nodes(input, &mut context).unwrap_err(),
+            nom::Err::Failure(HsmlError::new(input, HsmlErrorKind::UnclosedAttributeList))
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use nom::{
-    IResult, Needed,
+    IResult,
     bytes::complete::tag,
     error::{Error, ErrorKind},
 };
@@ -10,8 +10,10 @@ fn is_valid_attribute_key_start(c: char) -> bool {
     c.is_alphabetic() || c == ':' || c == '#' || c == '@' || c == '[' || c == '('
 }
 
-fn process_attribute_key(input: &str) -> IResult<&str, &str> {
-    let first_char = input.chars().next().expect("input is empty");
+pub fn process_attribute_key(input: &str) -> IResult<&str, &str> {
+    let Some(first_char) = input.chars().next() else {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Eof)));
+    };
 
     if first_char.is_numeric() {
         return Err(nom::Err::Error(Error::new(input, ErrorKind::AlphaNumeric)));
@@ -28,169 +30,139 @@ fn process_attribute_key(input: &str) -> IResult<&str, &str> {
     loop {
         // get first char and check if it is a `(`
         // if so, find the closing brace, because otherwise the closing brace is the end of the attributes
-        let first_char = remaining.get(..1);
+        let mut chars = remaining.chars();
+
+        let Some(first_char) = chars.next() else {
+            // we hit the end of input, the attribute list is checked for its closing brace afterwards
+            break;
+        };
 
         match first_char {
-            Some(")") => {
+            ')' => {
                 // we hit the end of the attributes, so we are done
                 break;
             }
-            Some(",") => {
+            ',' => {
                 // we hit a comma, so we are done
                 break;
             }
-            Some("=") => {
+            '=' => {
                 // we hit an equal sign, so we are done
                 break;
             }
-            Some(" ") => {
+            ' ' => {
                 // we hit a whitespace, so we are done
                 break;
             }
-            Some("\r") if remaining.get(1..2) == Some("\n") => {
+            '\r' if chars.next() == Some('\n') => {
                 // we hit a newline, so we are done
                 break;
             }
-            Some("\r") => {}
-            Some("\n") => {
+            '\n' => {
                 // we hit a newline, so we are done
                 break;
             }
-            Some("[") => {
-                // find the closing bracket
-                let closing_bracket = ']';
-
-                let mut closing_bracket_index = 0;
-                let mut is_escaped = false;
-
-                for (index, c) in remaining.chars().enumerate() {
-                    if index == 0 {
-                        // skip first char, because it is the opening bracket
-                        continue;
-                    }
-
-                    if c == '\\' {
-                        is_escaped = true;
-                        continue;
-                    }
-
-                    if c == closing_bracket && !is_escaped {
-                        closing_bracket_index = index;
-                        break;
-                    }
+            '[' | '(' => {
+                // find the closing bracket or brace
+                let closing_char = if first_char == '[' { ']' } else { ')' };
 
-                    is_escaped = false;
-                }
-
-                if closing_bracket_index == 0 {
+                let Some(closing_index) = find_closing_char(remaining, closing_char) else {
                     return Err(nom::Err::Error(Error::new(remaining, ErrorKind::Tag)));
-                }
-
-                attribute_key_index += closing_bracket_index;
-                remaining = input.get(attribute_key_index..).unwrap();
+                };
 
-                continue;
+                attribute_key_index += closing_index + closing_char.len_utf8();
             }
-            Some("(") => {
-                // find the closing brace
-                let closing_brace = ')';
-
-                let mut closing_brace_index = 0;
-                let mut is_escaped = false;
-
-                for (index, c) in remaining.chars().enumerate() {
-                    if index == 0 {
-                        // skip first char, because it is the opening brace
-                        continue;
-                    }
+            c => {
+                attribute_key_index += c.len_utf8();
+            }
+        }
 
-                    if c == '\\' {
-                        is_escaped = true;
-                        continue;
-                    }
+        remaining = &input[attribute_key_index..];
+    }
 
-                    if c == closing_brace && !is_escaped {
-                        closing_brace_index = index;
-                        break;
-                    }
+    let attribute_key = &input[..attribute_key_index];
 
-                    is_escaped = false;
-                }
+    Ok((remaining, attribute_key))
+}
 
-                if closing_brace_index == 0 {
-                    return Err(nom::Err::Error(Error::new(remaining, ErrorKind::Tag)));
-                }
+/// Byte index of the `closing_char` that is not escaped by a `\`, the first char of `input` is skipped.
+fn find_closing_char(input: &str, closing_char: char) -> Option<usize> {
+    let mut is_escaped = false;
 
-                attribute_key_index += closing_brace_index;
-                remaining = input.get(attribute_key_index + 1..).unwrap();
+    for (index, c) in input.char_indices().skip(1) {
+        if c == '\\' && !is_escaped {
+            is_escaped = true;
+            continue;
+        }
 
-                continue;
-            }
-            Some(_) => {
-                attribute_key_index += 1;
-                remaining = remaining.get(1..).unwrap();
-                continue;
-            }
-            None => {
-                return Err(nom::Err::Incomplete(Needed::Unknown));
-            }
+        if c == closing_char && !is_escaped {
+            return Some(index);
         }
-    }
 
-    let attribute_key = input.get(..attribute_key_index).unwrap();
+        is_escaped = false;
+    }
 
-    Ok((remaining, attribute_key))
+    None
 }
 
+/// Characters that end an unquoted attribute value, besides whitespace.
+///
+/// See https://html.spec.whatwg.org/multipage/syntax.html#unquoted
+const UNQUOTED_VALUE_DELIMITERS: [char; 8] = ['"', '\'', '=', '<', '>', '`', ')', ','];
+
 fn process_attribute_value<'a>(
     input: &'a str,
     _context: &mut HsmlProcessContext,
 ) -> IResult<&'a str, &'a str> {
     // get first char
-    let first_char = input.chars().next().unwrap();
+    let first_char = input.chars().next().unwrap_or(')');
 
     // if first char is a quote, then we need to find the closing quote and return the value in between (together with the surrounding quotes)
-    if first_char == '"' || first_char == '\'' {
-        let closing_quote = if first_char == '"' { '"' } else { '\'' };
-
-        let mut closing_quote_index = 0;
-        let mut is_escaped = false;
+    if first_char == '"' || first_char == '\'' || first_char == '`' {
+        let Some(closing_quote_index) = find_closing_char(input, first_char) else {
+            return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
+        };
 
-        for (index, c) in input.chars().enumerate() {
-            if index == 0 {
-                // skip first char, because it is the opening quote
-                continue;
-            }
+        return Ok((
+            &input[closing_quote_index + 1..],
+            &input[1..closing_quote_index],
+        ));
+    }
 
-            if c == '\\' {
-                is_escaped = true;
-                continue;
-            }
+    // otherwise it is an unquoted value like `width=384`
+    let value_end = input
+        .find(|c: char| c.is_whitespace() || UNQUOTED_VALUE_DELIMITERS.contains(&c))
+        .unwrap_or(input.len());
 
-            if c == closing_quote && !is_escaped {
-                closing_quote_index = index;
-                break;
-            }
+    if value_end == 0 {
+        // there is no valid attribute value
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
+    }
 
-            is_escaped = false;
-        }
+    Ok((&input[value_end..], &input[..value_end]))
+}
 
-        if closing_quote_index == 0 {
-            return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
+/// Decode the escape sequences of a quoted value, `\` followed by the quote or another `\` is replaced by that char.
+///
+/// Every other backslash is kept as is, so JavaScript in bindings stays untouched.
+pub fn decode_attribute_value(value: &str, quote: char) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && let Some(&next) = chars.peek()
+            && (next == quote || next == '\\')
+        {
+            decoded.push(next);
+            chars.next();
+            continue;
         }
 
-        let attribute_value = input.get(1..closing_quote_index).unwrap();
-
-        // dbg!(attribute_value);
-
-        return Ok((
-            input.get(closing_quote_index + 1..).unwrap_or(""),
-            attribute_value,
-        ));
+        decoded.push(c);
     }
 
-    // otherwise it was not a valid attribute value
-    Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+    decoded
 }
 
 // An attribute key can only contain a-z, A-Z, 0-9, `-`, `_`, `:`, `#`, `@`, `[`, `]`, `(`, `)`, `{`, `}`
@@ -216,9 +188,7 @@ pub fn process_attribute<'a>(
         let (remaining_after_attribute_value, _attribute_value) =
             process_attribute_value(remaining_after_equal_sign, context)?;
 
-        let attribute = input
-            .get(..input.len() - remaining_after_attribute_value.len())
-            .unwrap();
+        let attribute = &input[..input.len() - remaining_after_attribute_value.len()];
 
         return Ok((remaining_after_attribute_value, attribute));
     }
@@ -232,7 +202,10 @@ mod tests {
 
     use crate::parser::{
         HsmlProcessContext,
-        attribute::process::{process_attribute, process_attribute_key, process_attribute_value},
+        attribute::process::{
+            decode_attribute_value, process_attribute, process_attribute_key,
+            process_attribute_value,
+        },
     };
 
     #[test]
@@ -245,6 +218,16 @@ mod tests {
         assert_eq!(rest, ")");
     }
 
+    #[test]
+    fn it_should_process_attribute_key_at_end_of_input() {
+        let input = "disabled";
+
+        let (rest, attribute_key) = process_attribute_key(input).unwrap();
+
+        assert_eq!(attribute_key, "disabled");
+        assert_eq!(rest, "");
+    }
+
     #[test]
     fn it_should_process_attribute_value() {
         let input = r#""https://github.com/""#;
@@ -379,6 +362,7 @@ mod tests {
             &mut HsmlProcessContext {
                 indent_level: 1,
                 indent_string: Some(String::from("    ")),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -397,6 +381,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_should_process_attribute_with_unquoted_value() {
+        let input = "width=384, height=216)";
+
+        let (rest, attribute) =
+            process_attribute(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute, "width=384");
+        assert_eq!(rest, ", height=216)");
+    }
+
+    #[test]
+    fn it_should_process_attribute_with_single_quoted_value() {
+        let input = r#"alt='say "hi" \'twice\'' src="a.png""#;
+
+        let (rest, attribute) =
+            process_attribute(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute, r#"alt='say "hi" \'twice\''"#);
+        assert_eq!(rest, r#" src="a.png""#);
+    }
+
+    #[test]
+    fn it_should_process_attribute_with_backtick_value() {
+        let input = ":title=`Hello ${name}`)";
+
+        let (rest, attribute) =
+            process_attribute(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute, ":title=`Hello ${name}`");
+        assert_eq!(rest, ")");
+    }
+
+    #[test]
+    fn it_should_process_attribute_with_multibyte_characters() {
+        let input = r#"[ü]="日本語 \"😀\"", alt="" // this is empty 🤷"#;
+
+        let (rest, attribute) =
+            process_attribute(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute, r#"[ü]="日本語 \"😀\"""#);
+        assert_eq!(rest, r#", alt="" // this is empty 🤷"#);
+    }
+
+    #[test]
+    fn it_should_process_attribute_key_with_multibyte_characters() {
+        let input = "(klick·ü)=\"f()\")";
+
+        let (rest, attribute_key) = process_attribute_key(input).unwrap();
+
+        assert_eq!(attribute_key, "(klick·ü)");
+        assert_eq!(rest, "=\"f()\")");
+    }
+
+    #[test]
+    fn it_should_process_unquoted_attribute_value_with_multibyte_characters() {
+        let input = "grüße🎉, title=x)";
+
+        let (rest, attribute_value) =
+            process_attribute_value(input, &mut HsmlProcessContext::default()).unwrap();
+
+        assert_eq!(attribute_value, "grüße🎉");
+        assert_eq!(rest, ", title=x)");
+    }
+
+    #[test]
+    fn it_should_decode_attribute_value() {
+        assert_eq!(
+            decode_attribute_value(r#"say \"hi\" \\ \n \'"#, '"'),
+            r#"say "hi" \ \n \'"#
+        );
+        assert_eq!(decode_attribute_value(r"it\'s", '\''), "it's");
+        assert_eq!(decode_attribute_value(r"\`${a}\`", '`'), "`${a}`");
+    }
+
     // Negative tests
 
     #[test]
@@ -452,12 +511,12 @@ mod tests {
     }
 
     #[test]
-    fn it_should_not_process_attribute_without_quoted_value() {
-        let input = "src=imgSrc";
+    fn it_should_not_process_attribute_without_value() {
+        let input = "src=)";
 
         assert_eq!(
             Err(nom::Err::Error(Error {
-                input: "imgSrc",
+                input: ")",
                 code: ErrorKind::Tag
             })),
             process_attribute(input, &mut HsmlProcessContext::default())
@@ -1,19 +1,28 @@
-use nom::IResult;
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, error::HsmlResult, span::Span};
 
 use super::process::process_class;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename = "class", rename_all = "camelCase")]
 pub struct ClassNode {
     pub name: String,
+    pub span: Span,
 }
 
-pub fn class_node(input: &str) -> IResult<&str, ClassNode> {
-    let (input, class_name) = process_class(input)?;
+pub fn class_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+    is_interpolated: bool,
+) -> HsmlResult<'a, ClassNode> {
+    let (rest, class_name) = process_class(input, is_interpolated).map_err(nom::Err::convert)?;
 
     Ok((
-        input,
+        rest,
         ClassNode {
             name: class_name.to_string(),
+            span: context.span_between(input, rest),
         },
     ))
 }
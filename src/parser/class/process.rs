@@ -1,52 +1,63 @@
 use nom::{
-    IResult, Needed,
+    IResult,
     bytes::complete::tag,
     error::{Error, ErrorKind},
 };
 
-pub fn process_class(input: &str) -> IResult<&str, &str> {
+pub fn process_class(input: &str, is_interpolated: bool) -> IResult<&str, &str> {
     let (input, _) = tag(".")(input)?;
 
+    process_name(input, is_interpolated)
+}
+
+/// Process a class or id name up to the next delimiter or the end of the input.
+///
+/// Arbitrary values in brackets like `[&>*]` can contain delimiters, a `\` escapes the closing bracket.
+/// Inside of an interpolated tag, a `]` ends the name as well.
+pub fn process_name(input: &str, is_interpolated: bool) -> IResult<&str, &str> {
     let mut remaining = input;
 
-    let mut class_index = 0;
+    let mut name_index = 0;
 
     loop {
-        // get first char and check if it is a `[`
-        // if so, it is an arbitrary tailwind value
-        let first_char = remaining.get(..1);
+        let mut chars = remaining.chars();
+
+        let Some(first_char) = chars.next() else {
+            // we hit the end of the input, so we are done
+            break;
+        };
+        let second_char = chars.next();
 
-        match first_char {
-            Some("#") => {
+        match (first_char, second_char) {
+            ('#', _) => {
                 // we hit a id, so we are done
                 break;
             }
-            Some(".") => {
+            ('.', _) => {
                 // we hit a new class, so we are done
                 break;
             }
-            Some("(") => {
+            ('(', _) => {
                 // we hit the start of attributes, so we are done
                 break;
             }
-            Some(" ") => {
-                // we hit a whitespace, so we are done
+            ('!', Some('=')) => {
+                // we hit unescaped text, so we are done
                 break;
             }
-            Some("\t") => {
-                // we hit a tab, so we are done
+            (':', Some(' ')) => {
+                // we hit a block expansion, so we are done
                 break;
             }
-            Some("\r") if remaining.get(1..2) == Some("\n") => {
-                // we hit a newline, so we are done
+            (']', _) if is_interpolated => {
+                // we hit the end of an interpolated tag, so we are done
                 break;
             }
-            Some("\r") => {}
-            Some("\n") => {
-                // we hit a newline, so we are done
+            (c, _) if c.is_whitespace() => {
+                // we hit a whitespace or newline, so we are done
                 break;
             }
-            Some("[") => {
+            ('[', _) => {
                 // Parse arbitrary tailwind values (https://tailwindcss.com/docs/adding-custom-styles#using-arbitrary-values)
 
                 let closing_bracket = ']';
@@ -54,13 +65,8 @@ pub fn process_class(input: &str) -> IResult<&str, &str> {
                 let mut closing_bracket_index = 0;
                 let mut is_escaped = false;
 
-                for (index, c) in remaining.chars().enumerate() {
-                    if index == 0 {
-                        // skip first char, because it is the opening bracket
-                        continue;
-                    }
-
-                    if c == '\\' {
+                for (index, c) in remaining.char_indices().skip(1) {
+                    if c == '\\' && !is_escaped {
                         is_escaped = true;
                         continue;
                     }
@@ -77,26 +83,19 @@ pub fn process_class(input: &str) -> IResult<&str, &str> {
                     return Err(nom::Err::Error(Error::new(remaining, ErrorKind::Tag)));
                 }
 
-                class_index += closing_bracket_index;
-                remaining = input.get(class_index..).unwrap();
-
-                continue;
+                // the closing bracket belongs to the name
+                name_index += closing_bracket_index + closing_bracket.len_utf8();
             }
-            Some(_) => {
-                // we hit a char, so we need to append it to the class
-                class_index += 1;
-                remaining = remaining.get(1..).unwrap();
-                continue;
-            }
-            None => {
-                return Err(nom::Err::Incomplete(Needed::Unknown));
+            (c, _) => {
+                // we hit a char, so we need to append it to the name
+                name_index += c.len_utf8();
             }
         }
-    }
 
-    let class = input.get(..class_index).unwrap();
+        remaining = &input[name_index..];
+    }
 
-    Ok((remaining, class))
+    Ok((remaining, &input[..name_index]))
 }
 
 #[cfg(test)]
@@ -109,7 +108,7 @@ mod tests {
     fn it_should_process_class_with_text() {
         let input = ".text-red Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "text-red");
         assert_eq!(rest, " Text");
@@ -119,7 +118,7 @@ mod tests {
     fn it_should_process_class_with_colon() {
         let input = ".focus:outline-none Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "focus:outline-none");
         assert_eq!(rest, " Text");
@@ -129,7 +128,7 @@ mod tests {
     fn it_should_process_class_with_arbitrary_tailwind_value() {
         let input = ".bg-[#1da1f2]#name Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "bg-[#1da1f2]");
         assert_eq!(rest, "#name Text");
@@ -139,7 +138,7 @@ mod tests {
     fn it_should_process_class_with_arbitrary_tailwind_value_2() {
         let input = ".lg:[&:nth-child(3)]:hover:underline#name Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "lg:[&:nth-child(3)]:hover:underline");
         assert_eq!(rest, "#name Text");
@@ -149,17 +148,31 @@ mod tests {
     fn it_should_process_class_with_arbitrary_tailwind_value_3() {
         let input = ".bg-[url('/what_a_rush.png')]#name Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "bg-[url('/what_a_rush.png')]");
         assert_eq!(rest, "#name Text");
     }
 
+    #[test]
+    fn it_should_process_class_with_multibyte_characters() {
+        let input = ".größe-🎉.bg-[url('日本.png')]#ü Text";
+
+        let (rest, class) = process_class(input, false).unwrap();
+
+        assert_eq!(class, "größe-🎉");
+
+        let (rest, class) = process_class(rest, false).unwrap();
+
+        assert_eq!(class, "bg-[url('日本.png')]");
+        assert_eq!(rest, "#ü Text");
+    }
+
     #[test]
     fn it_should_process_class_with_id() {
         let input = ".text-red#name Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "text-red");
         assert_eq!(rest, "#name Text");
@@ -169,7 +182,7 @@ mod tests {
     fn it_should_process_class_with_attribute() {
         let input = ".text-red(disabled) Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "text-red");
         assert_eq!(rest, "(disabled) Text");
@@ -179,7 +192,7 @@ mod tests {
     fn it_should_process_class_with_whitespace() {
         let input = ".text-red Text";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "text-red");
         assert_eq!(rest, " Text");
@@ -189,7 +202,7 @@ mod tests {
     fn it_should_process_class_with_tab() {
         let input = ".text-red\t";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "text-red");
         assert_eq!(rest, "\t");
@@ -199,7 +212,7 @@ mod tests {
     fn it_should_process_class_with_line_ending() {
         let input = ".text-red\n";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "text-red");
         assert_eq!(rest, "\n");
@@ -209,12 +222,42 @@ mod tests {
     fn it_should_process_class_with_crlf() {
         let input = ".text-red\r\n";
 
-        let (rest, class) = process_class(input).unwrap();
+        let (rest, class) = process_class(input, false).unwrap();
 
         assert_eq!(class, "text-red");
         assert_eq!(rest, "\r\n");
     }
 
+    #[test]
+    fn it_should_process_class_with_block_expansion() {
+        let input = ".hover:underline: a Text";
+
+        let (rest, class) = process_class(input, false).unwrap();
+
+        assert_eq!(class, "hover:underline");
+        assert_eq!(rest, ": a Text");
+    }
+
+    #[test]
+    fn it_should_process_class_with_closing_bracket() {
+        let input = ".a]b Text";
+
+        let (rest, class) = process_class(input, false).unwrap();
+
+        assert_eq!(class, "a]b");
+        assert_eq!(rest, " Text");
+    }
+
+    #[test]
+    fn it_should_process_interpolated_class() {
+        let input = ".a] Text";
+
+        let (rest, class) = process_class(input, true).unwrap();
+
+        assert_eq!(class, "a");
+        assert_eq!(rest, "] Text");
+    }
+
     // Negative tests
 
     #[test]
@@ -226,7 +269,7 @@ mod tests {
                 input: "text-red(disabled) Text",
                 code: ErrorKind::Tag
             })),
-            process_class(input)
+            process_class(input, false)
         );
 
         let input = "#text-red(disabled) Text";
@@ -236,7 +279,7 @@ mod tests {
                 input: "#text-red(disabled) Text",
                 code: ErrorKind::Tag
             })),
-            process_class(input)
+            process_class(input, false)
         );
     }
 }
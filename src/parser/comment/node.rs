@@ -1,50 +1,70 @@
-use nom::IResult;
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, error::HsmlResult, span::Span};
 
 use super::process::{process_dev_comment, process_native_comment};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename = "comment", rename_all = "camelCase")]
 pub struct CommentNode {
     pub text: String,
     pub is_dev: bool,
+    pub span: Span,
 }
 
-pub fn comment_dev_node(input: &str) -> IResult<&str, CommentNode> {
-    let (input, comment) = process_dev_comment(input)?;
+pub fn comment_dev_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, CommentNode> {
+    let (rest, comment) = process_dev_comment(input).map_err(nom::Err::convert)?;
 
     Ok((
-        input,
+        rest,
         CommentNode {
             text: comment.to_string(),
             is_dev: true,
+            span: context.span_between(input, rest),
         },
     ))
 }
 
-pub fn comment_native_node(input: &str) -> IResult<&str, CommentNode> {
-    let (input, comment) = process_native_comment(input)?;
+pub fn comment_native_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, CommentNode> {
+    let (rest, comment) = process_native_comment(input).map_err(nom::Err::convert)?;
 
     Ok((
-        input,
+        rest,
         CommentNode {
             text: comment.to_string(),
             is_dev: false,
+            span: context.span_between(input, rest),
         },
     ))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::comment::node::{CommentNode, comment_dev_node, comment_native_node};
+    use crate::parser::{
+        HsmlProcessContext,
+        comment::node::{CommentNode, comment_dev_node, comment_native_node},
+        span::{Position, Span},
+    };
 
     #[test]
     fn it_should_return_comment_dev_node() {
-        let (input, comment) = comment_dev_node("// This is a dev comment\n").unwrap();
+        let input = "// This is a dev comment\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, comment) = comment_dev_node(input, &context).unwrap();
 
         assert_eq!(
             comment,
             CommentNode {
                 text: String::from(" This is a dev comment"),
                 is_dev: true,
+                span: Span::new(Position::new(0, 1, 1), Position::new(24, 1, 25)),
             }
         );
 
@@ -53,13 +73,17 @@ mod tests {
 
     #[test]
     fn it_should_return_comment_native_node() {
-        let (input, comment) = comment_native_node("//! This is a native comment\n").unwrap();
+        let input = "//! This is a native comment\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, comment) = comment_native_node(input, &context).unwrap();
 
         assert_eq!(
             comment,
             CommentNode {
                 text: String::from(" This is a native comment"),
                 is_dev: false,
+                span: Span::new(Position::new(0, 1, 1), Position::new(28, 1, 29)),
             }
         );
 
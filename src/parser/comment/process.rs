@@ -1,32 +1,36 @@
 use nom::{
     IResult,
-    bytes::complete::{tag, take_until},
+    bytes::complete::{tag, take_till},
     error::{Error, ErrorKind},
 };
 
+/// Read until end of line or end of input, the `\r` of a `\r\n` line ending is not part of the comment.
+fn process_comment_text(input: &str) -> IResult<&str, &str> {
+    let (rest, comment) = take_till(|c: char| c == '\n')(input)?;
+
+    match comment.strip_suffix('\r') {
+        Some(comment) => Ok((&input[comment.len()..], comment)),
+        None => Ok((rest, comment)),
+    }
+}
+
 pub fn process_dev_comment(input: &str) -> IResult<&str, &str> {
     let (input, _) = tag("//")(input)?;
 
     // check next char is not a `!`
-    if let Some(c) = input.chars().next() {
-        if c == '!' {
-            return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
-        }
+    if let Some(c) = input.chars().next()
+        && c == '!'
+    {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
     }
 
-    // read until end of line
-    let (input, comment) = take_until("\n")(input)?;
-
-    Ok((input, comment))
+    process_comment_text(input)
 }
 
 pub fn process_native_comment(input: &str) -> IResult<&str, &str> {
     let (input, _) = tag("//!")(input)?;
 
-    // read until end of line
-    let (input, comment) = take_until("\n")(input)?;
-
-    Ok((input, comment))
+    process_comment_text(input)
 }
 
 #[cfg(test)]
@@ -55,6 +59,32 @@ mod tests {
         assert_eq!(rest, "\n");
     }
 
+    #[test]
+    fn it_should_process_comments_with_crlf() {
+        let (rest, comment) = process_dev_comment("// dev\r\n").unwrap();
+
+        assert_eq!(comment, " dev");
+        assert_eq!(rest, "\r\n");
+
+        let (rest, comment) = process_native_comment("//! native\r\n").unwrap();
+
+        assert_eq!(comment, " native");
+        assert_eq!(rest, "\r\n");
+    }
+
+    #[test]
+    fn it_should_process_comments_at_end_of_input() {
+        let (rest, comment) = process_dev_comment("// last line").unwrap();
+
+        assert_eq!(comment, " last line");
+        assert_eq!(rest, "");
+
+        let (rest, comment) = process_native_comment("//! last line").unwrap();
+
+        assert_eq!(comment, " last line");
+        assert_eq!(rest, "");
+    }
+
     // Negative tests
 
     #[test]
@@ -0,0 +1,67 @@
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, error::HsmlResult, span::Span};
+
+use super::process::process_doctype;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename = "doctype", rename_all = "camelCase")]
+pub struct DoctypeNode {
+    /// Either a shortcut like `html` or `strict` or a custom doctype, a bare `doctype` is `html`
+    pub value: String,
+    pub span: Span,
+}
+
+pub fn doctype_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, DoctypeNode> {
+    let (rest, value) = process_doctype(input).map_err(nom::Err::convert)?;
+
+    let value = if value.is_empty() { "html" } else { value };
+
+    Ok((
+        rest,
+        DoctypeNode {
+            value: value.to_string(),
+            span: context.span_between(input, rest),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{
+        HsmlProcessContext,
+        doctype::node::{DoctypeNode, doctype_node},
+        span::{Position, Span},
+    };
+
+    #[test]
+    fn it_should_return_doctype_node() {
+        let input = "doctype strict\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, doctype) = doctype_node(input, &context).unwrap();
+
+        assert_eq!(
+            doctype,
+            DoctypeNode {
+                value: String::from("strict"),
+                span: Span::new(Position::new(0, 1, 1), Position::new(14, 1, 15)),
+            }
+        );
+
+        assert_eq!(input, "\n");
+    }
+
+    #[test]
+    fn it_should_default_to_html() {
+        let input = "doctype\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (_, doctype) = doctype_node(input, &context).unwrap();
+
+        assert_eq!(doctype.value, "html");
+    }
+}
@@ -0,0 +1,72 @@
+use nom::{
+    IResult,
+    bytes::complete::{tag, take_till},
+};
+
+/// Process `doctype` followed by an optional value until the end of the line.
+pub fn process_doctype(input: &str) -> IResult<&str, &str> {
+    let (input, _) = tag("doctype")(input)?;
+
+    // a bare `doctype` has no value
+    if input.is_empty() || input.starts_with('\n') || input.starts_with("\r\n") {
+        return Ok((input, ""));
+    }
+
+    let (input, _) = tag(" ")(input)?;
+
+    // read until end of line
+    let (input, value) = take_till(|c: char| c == '\n' || c == '\r')(input)?;
+
+    Ok((input, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::{Error, ErrorKind};
+
+    use crate::parser::doctype::process::process_doctype;
+
+    #[test]
+    fn it_should_process_doctype() {
+        let input = "doctype html\nhtml";
+
+        let (rest, value) = process_doctype(input).unwrap();
+
+        assert_eq!(value, "html");
+        assert_eq!(rest, "\nhtml");
+    }
+
+    #[test]
+    fn it_should_process_custom_doctype() {
+        let input = r#"doctype html PUBLIC "-//W3C//DTD XHTML Basic 1.1//EN""#;
+
+        let (rest, value) = process_doctype(input).unwrap();
+
+        assert_eq!(value, r#"html PUBLIC "-//W3C//DTD XHTML Basic 1.1//EN""#);
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_process_doctype_without_value() {
+        let input = "doctype\r\nhtml";
+
+        let (rest, value) = process_doctype(input).unwrap();
+
+        assert_eq!(value, "");
+        assert_eq!(rest, "\r\nhtml");
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_process_tag_starting_with_doctype() {
+        let input = "doctypes";
+
+        let result = process_doctype(input);
+
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(Error::new("s", ErrorKind::Tag)))
+        );
+    }
+}
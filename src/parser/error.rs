@@ -0,0 +1,223 @@
+use std::fmt;
+
+use nom::{
+    IResult,
+    error::{ErrorKind, ParseError},
+};
+
+use crate::parser::{HsmlProcessContext, span::Position};
+
+/// An error that occurred while parsing a hsml source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HsmlParseError {
+    DuplicateId {
+        id: String,
+        position: Position,
+    },
+    MixedIndentation {
+        position: Position,
+    },
+    UnclosedAttributeList {
+        position: Position,
+    },
+    UnterminatedQuote {
+        quote: char,
+        position: Position,
+    },
+    InvalidTagName {
+        name: String,
+        position: Position,
+    },
+    UnexpectedIndent {
+        position: Position,
+    },
+    UnclosedInterpolation {
+        position: Position,
+    },
+    RawTextEndTag {
+        tag: String,
+        position: Position,
+    },
+    /// `found` is `None` if the end of the input was reached
+    UnexpectedInput {
+        found: Option<char>,
+        position: Position,
+    },
+}
+
+impl HsmlParseError {
+    pub fn position(&self) -> Position {
+        match self {
+            HsmlParseError::DuplicateId { position, .. }
+            | HsmlParseError::MixedIndentation { position }
+            | HsmlParseError::UnclosedAttributeList { position }
+            | HsmlParseError::UnterminatedQuote { position, .. }
+            | HsmlParseError::InvalidTagName { position, .. }
+            | HsmlParseError::UnexpectedIndent { position }
+            | HsmlParseError::UnclosedInterpolation { position }
+            | HsmlParseError::RawTextEndTag { position, .. }
+            | HsmlParseError::UnexpectedInput { position, .. } => *position,
+        }
+    }
+
+    /// A stable identifier of the kind of error, e.g. for tooling.
+    pub fn code(&self) -> &'static str {
+        match self {
+            HsmlParseError::DuplicateId { .. } => "duplicate-id",
+            HsmlParseError::MixedIndentation { .. } => "mixed-indentation",
+            HsmlParseError::UnclosedAttributeList { .. } => "unclosed-attribute-list",
+            HsmlParseError::UnterminatedQuote { .. } => "unterminated-quote",
+            HsmlParseError::InvalidTagName { .. } => "invalid-tag-name",
+            HsmlParseError::UnexpectedIndent { .. } => "unexpected-indent",
+            HsmlParseError::UnclosedInterpolation { .. } => "unclosed-interpolation",
+            HsmlParseError::RawTextEndTag { .. } => "raw-text-end-tag",
+            HsmlParseError::UnexpectedInput { .. } => "unexpected-input",
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            HsmlParseError::DuplicateId { id, .. } => {
+                format!("duplicate id `{id}`, a tag can only have one id")
+            }
+            HsmlParseError::MixedIndentation { .. } => String::from("mixed indentation"),
+            HsmlParseError::UnclosedAttributeList { .. } => {
+                String::from("unclosed attribute list, expected `)`")
+            }
+            HsmlParseError::UnterminatedQuote { quote, .. } => {
+                format!("unterminated quote, expected closing `{quote}`")
+            }
+            HsmlParseError::InvalidTagName { name, .. } => format!("invalid tag name `{name}`"),
+            HsmlParseError::UnexpectedIndent { .. } => String::from("unexpected indentation"),
+            HsmlParseError::UnclosedInterpolation { .. } => {
+                String::from("unclosed interpolation, expected `]`")
+            }
+            HsmlParseError::RawTextEndTag { tag, .. } => {
+                format!("`</{tag}` would end the `{tag}` element early")
+            }
+            HsmlParseError::UnexpectedInput { found: Some(c), .. } => {
+                format!("unexpected `{}`", c.escape_debug())
+            }
+            HsmlParseError::UnexpectedInput { found: None, .. } => {
+                String::from("unexpected end of input")
+            }
+        }
+    }
+
+    /// A short suggestion on how to fix the error, if there is one.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            HsmlParseError::DuplicateId { .. } => Some("remove one of the ids"),
+            HsmlParseError::MixedIndentation { .. } => Some("mixed tabs and spaces in indentation"),
+            HsmlParseError::UnclosedAttributeList { .. } => {
+                Some("close the attribute list with `)`")
+            }
+            HsmlParseError::UnterminatedQuote { .. } => {
+                Some("quotes inside of a value have to be escaped with `\\`")
+            }
+            HsmlParseError::InvalidTagName { .. } => Some(
+                "tag names have to start with a letter and may only contain letters, digits and `-`",
+            ),
+            HsmlParseError::UnexpectedIndent { .. } => {
+                Some("child nodes have to be indented exactly one level deeper than their parent")
+            }
+            HsmlParseError::UnclosedInterpolation { .. } => {
+                Some("close the interpolation with `]` on the same line")
+            }
+            HsmlParseError::RawTextEndTag { .. } => {
+                Some("inside of strings the slash can be escaped like `<\\/script>`")
+            }
+            HsmlParseError::UnexpectedInput { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for HsmlParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = self.position();
+
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message(),
+            position.line,
+            position.column
+        )
+    }
+}
+
+impl std::error::Error for HsmlParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HsmlErrorKind {
+    DuplicateId(String),
+    MixedIndentation,
+    UnclosedAttributeList,
+    UnterminatedQuote(char),
+    InvalidTagName(String),
+    UnexpectedIndent,
+    UnclosedInterpolation,
+    RawTextEndTag(String),
+    Nom(ErrorKind),
+}
+
+/// The error the node parsers work with.
+///
+/// It only knows the remaining input where it occurred and gets resolved into a [`HsmlParseError`]
+/// with a [`Position`] once parsing stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HsmlError<'a> {
+    pub input: &'a str,
+    pub kind: HsmlErrorKind,
+}
+
+pub type HsmlResult<'a, O> = IResult<&'a str, O, HsmlError<'a>>;
+
+impl<'a> HsmlError<'a> {
+    pub fn new(input: &'a str, kind: HsmlErrorKind) -> Self {
+        Self { input, kind }
+    }
+
+    pub fn into_parse_error(self, context: &HsmlProcessContext) -> HsmlParseError {
+        let position = context.position(context.offset(self.input));
+
+        match self.kind {
+            HsmlErrorKind::DuplicateId(id) => HsmlParseError::DuplicateId { id, position },
+            HsmlErrorKind::MixedIndentation => HsmlParseError::MixedIndentation { position },
+            HsmlErrorKind::UnclosedAttributeList => {
+                HsmlParseError::UnclosedAttributeList { position }
+            }
+            HsmlErrorKind::UnterminatedQuote(quote) => {
+                HsmlParseError::UnterminatedQuote { quote, position }
+            }
+            HsmlErrorKind::InvalidTagName(name) => {
+                HsmlParseError::InvalidTagName { name, position }
+            }
+            HsmlErrorKind::UnexpectedIndent => HsmlParseError::UnexpectedIndent { position },
+            HsmlErrorKind::UnclosedInterpolation => {
+                HsmlParseError::UnclosedInterpolation { position }
+            }
+            HsmlErrorKind::RawTextEndTag(tag) => HsmlParseError::RawTextEndTag { tag, position },
+            HsmlErrorKind::Nom(_) => HsmlParseError::UnexpectedInput {
+                found: self.input.chars().next(),
+                position,
+            },
+        }
+    }
+}
+
+impl<'a> ParseError<&'a str> for HsmlError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self::new(input, HsmlErrorKind::Nom(kind))
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> From<nom::error::Error<&'a str>> for HsmlError<'a> {
+    fn from(error: nom::error::Error<&'a str>) -> Self {
+        Self::new(error.input, HsmlErrorKind::Nom(error.code))
+    }
+}
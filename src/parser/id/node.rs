@@ -1,14 +1,28 @@
-use nom::IResult;
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, error::HsmlResult, span::Span};
 
 use super::process::process_id;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename = "id", rename_all = "camelCase")]
 pub struct IdNode {
     pub id: String,
+    pub span: Span,
 }
 
-pub fn id_node(input: &str) -> IResult<&str, IdNode> {
-    let (input, id) = process_id(input)?;
+pub fn id_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+    is_interpolated: bool,
+) -> HsmlResult<'a, IdNode> {
+    let (rest, id) = process_id(input, is_interpolated).map_err(nom::Err::convert)?;
 
-    Ok((input, IdNode { id: id.to_string() }))
+    Ok((
+        rest,
+        IdNode {
+            id: id.to_string(),
+            span: context.span_between(input, rest),
+        },
+    ))
 }
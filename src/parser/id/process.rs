@@ -1,11 +1,22 @@
-use nom::{IResult, bytes::complete::tag};
+use nom::{
+    IResult,
+    bytes::complete::tag,
+    error::{Error, ErrorKind},
+};
 
-pub fn process_id(input: &str) -> IResult<&str, &str> {
+use crate::parser::class::process::process_name;
+
+/// Process an id, which like in HTML can contain anything but whitespace up to the next delimiter.
+pub fn process_id(input: &str, is_interpolated: bool) -> IResult<&str, &str> {
     let (input, _) = tag("#")(input)?;
 
-    let (input, id) = nom::character::complete::alphanumeric1(input)?;
+    let (rest, id) = process_name(input, is_interpolated)?;
+
+    if id.is_empty() {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::TakeWhile1)));
+    }
 
-    Ok((input, id))
+    Ok((rest, id))
 }
 
 #[cfg(test)]
@@ -18,7 +29,7 @@ mod tests {
     fn it_should_process_id_with_text() {
         let input = "#id1 Text";
 
-        let (rest, id) = process_id(input).unwrap();
+        let (rest, id) = process_id(input, false).unwrap();
 
         assert_eq!(id, "id1");
         assert_eq!(rest, " Text");
@@ -28,7 +39,7 @@ mod tests {
     fn it_should_process_id_with_class() {
         let input = "#id1.text-red Text";
 
-        let (rest, id) = process_id(input).unwrap();
+        let (rest, id) = process_id(input, false).unwrap();
 
         assert_eq!(id, "id1");
         assert_eq!(rest, ".text-red Text");
@@ -38,14 +49,92 @@ mod tests {
     fn it_should_process_id_with_start_attribute() {
         let input = "#id1(hidden) Text";
 
-        let (rest, id) = process_id(input).unwrap();
+        let (rest, id) = process_id(input, false).unwrap();
 
         assert_eq!(id, "id1");
         assert_eq!(rest, "(hidden) Text");
     }
 
+    #[test]
+    fn it_should_process_id_with_hyphen_and_underscore() {
+        let input = "#main-content_2.wrapper Text";
+
+        let (rest, id) = process_id(input, false).unwrap();
+
+        assert_eq!(id, "main-content_2");
+        assert_eq!(rest, ".wrapper Text");
+    }
+
+    #[test]
+    fn it_should_process_id_with_unicode() {
+        let input = "#größe-😀 Text";
+
+        let (rest, id) = process_id(input, false).unwrap();
+
+        assert_eq!(id, "größe-😀");
+        assert_eq!(rest, " Text");
+    }
+
+    #[test]
+    fn it_should_process_id_with_brackets() {
+        let input = "#item[a.b\\]c](hidden)";
+
+        let (rest, id) = process_id(input, false).unwrap();
+
+        assert_eq!(id, "item[a.b\\]c]");
+        assert_eq!(rest, "(hidden)");
+    }
+
+    #[test]
+    fn it_should_process_id_at_end_of_input() {
+        let input = "#a:b";
+
+        let (rest, id) = process_id(input, false).unwrap();
+
+        assert_eq!(id, "a:b");
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_process_id_with_block_expansion() {
+        let input = "#nav: a";
+
+        let (rest, id) = process_id(input, false).unwrap();
+
+        assert_eq!(id, "nav");
+        assert_eq!(rest, ": a");
+    }
+
+    #[test]
+    fn it_should_process_id_with_closing_bracket() {
+        let input = "#a]b Text";
+
+        let (rest, id) = process_id(input, false).unwrap();
+
+        assert_eq!(id, "a]b");
+        assert_eq!(rest, " Text");
+
+        let (rest, id) = process_id(input, true).unwrap();
+
+        assert_eq!(id, "a");
+        assert_eq!(rest, "]b Text");
+    }
+
     // Negative tests
 
+    #[test]
+    fn it_should_not_process_empty_id() {
+        let input = "# Text";
+
+        assert_eq!(
+            Err(nom::Err::Error(Error {
+                input: " Text",
+                code: ErrorKind::TakeWhile1
+            })),
+            process_id(input, false)
+        );
+    }
+
     #[test]
     fn it_should_not_process_id_without_hash() {
         let input = "id1(disabled) Text";
@@ -55,7 +144,7 @@ mod tests {
                 input: "id1(disabled) Text",
                 code: ErrorKind::Tag
             })),
-            process_id(input)
+            process_id(input, false)
         );
 
         let input = ".text-red(disabled) Text";
@@ -65,7 +154,7 @@ mod tests {
                 input: ".text-red(disabled) Text",
                 code: ErrorKind::Tag
             })),
-            process_id(input)
+            process_id(input, false)
         );
     }
 }
@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+use crate::parser::RootNode;
+
+/// Version of the JSON representation of the AST.
+///
+/// It gets bumped on every change to the shape of the output, so consumers can detect whether they understand it.
+pub const AST_VERSION: u32 = 9;
+
+#[derive(Serialize)]
+struct VersionedAst<'a> {
+    version: u32,
+    ast: &'a RootNode,
+}
+
+/// Serialize the AST as `{ "version": AST_VERSION, "ast": { "type": "root", ... } }`.
+pub fn to_json(root_node: &RootNode, pretty: bool) -> serde_json::Result<String> {
+    let versioned_ast = VersionedAst {
+        version: AST_VERSION,
+        ast: root_node,
+    };
+
+    if pretty {
+        serde_json::to_string_pretty(&versioned_ast)
+    } else {
+        serde_json::to_string(&versioned_ast)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{json::to_json, parse::parse};
+
+    #[test]
+    fn it_should_serialize_ast() {
+        let root_node = parse("//! hi\np#intro.lead(hidden) Text\n").unwrap();
+
+        let json = to_json(&root_node, false).unwrap();
+
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"version":9,"ast":{"type":"root","nodes":["#,
+                r#"{"type":"comment","text":" hi","isDev":false,"span":{"start":{"offset":0,"line":1,"column":1},"end":{"offset":6,"line":1,"column":7}}},"#,
+                r#"{"type":"tag","tag":"p","#,
+                r#""id":{"type":"id","id":"intro","span":{"start":{"offset":8,"line":2,"column":2},"end":{"offset":14,"line":2,"column":8}}},"#,
+                r#""classes":[{"type":"class","name":"lead","span":{"start":{"offset":14,"line":2,"column":8},"end":{"offset":19,"line":2,"column":13}}}],"#,
+                r#""attributes":[{"type":"attribute","key":"hidden","value":null,"rawValue":null,"quote":null,"isUnescaped":false,"span":{"start":{"offset":20,"line":2,"column":14},"end":{"offset":26,"line":2,"column":20}}}],"#,
+                r#""text":{"type":"text","text":"Text","rawText":"Text","isUnescaped":false,"segments":null,"span":{"start":{"offset":28,"line":2,"column":22},"end":{"offset":32,"line":2,"column":26}}},"#,
+                r#""children":null,"isBlockExpansion":false,"#,
+                r#""span":{"start":{"offset":7,"line":2,"column":1},"end":{"offset":32,"line":2,"column":26}}}"#,
+                r#"]}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_serialize_ast_pretty() {
+        let root_node = parse("br\n").unwrap();
+
+        let json = to_json(&root_node, true).unwrap();
+
+        assert!(json.starts_with("{\n  \"version\": 9,\n  \"ast\": {\n    \"type\": \"root\","));
+    }
+}
@@ -1,38 +1,113 @@
+use std::cell::OnceCell;
+
 use nom::{IResult, character::complete::line_ending};
+use serde::Serialize;
 
 use self::{
-    attribute::node::AttributeNode, class::node::ClassNode, comment::node::CommentNode,
-    id::node::IdNode, tag::node::TagNode, text::node::TextNode,
+    attribute::node::AttributeNode,
+    class::node::ClassNode,
+    comment::node::CommentNode,
+    doctype::node::DoctypeNode,
+    id::node::IdNode,
+    raw_html::node::RawHtmlNode,
+    span::{Position, Span},
+    tag::node::TagNode,
+    text::node::TextNode,
 };
 
 pub mod attribute;
 pub mod class;
 pub mod comment;
+pub mod doctype;
+pub mod error;
 pub mod id;
+pub mod json;
 pub mod parse;
+pub mod raw_html;
+pub mod span;
 pub mod tag;
 pub mod text;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "root")]
 pub struct RootNode {
     pub nodes: Vec<HsmlNode>,
 }
 
-#[derive(Debug, PartialEq)]
+/// Every node serializes its own `type`, so the enum itself is untagged.
+// tag nodes dominate the tree, boxing them would only add indirection
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
 pub enum HsmlNode {
     Root(RootNode),
+    Doctype(DoctypeNode),
     Tag(TagNode),
     Comment(CommentNode),
     Id(IdNode),
     Class(ClassNode),
     Attribute(AttributeNode),
     Text(TextNode),
+    RawHtml(RawHtmlNode),
+}
+
+impl HsmlNode {
+    /// The span of the node, only the root node has none.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            HsmlNode::Root(_) => None,
+            HsmlNode::Doctype(node) => Some(node.span),
+            HsmlNode::Tag(node) => Some(node.span),
+            HsmlNode::Comment(node) => Some(node.span),
+            HsmlNode::Id(node) => Some(node.span),
+            HsmlNode::Class(node) => Some(node.span),
+            HsmlNode::Attribute(node) => Some(node.span),
+            HsmlNode::Text(node) => Some(node.span),
+            HsmlNode::RawHtml(node) => Some(node.span),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
-pub struct HsmlProcessContext {
+pub struct HsmlProcessContext<'a> {
+    /// The complete source, every input handed to the parsers is a suffix of it
+    pub source: &'a str,
     pub indent_level: usize,
     pub indent_string: Option<String>,
+    line_starts: OnceCell<Vec<usize>>,
+}
+
+impl<'a> HsmlProcessContext<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            ..Default::default()
+        }
+    }
+
+    /// Byte offset of `remaining` inside the source.
+    ///
+    /// `remaining` has to be a suffix of the source, which holds for every input and rest the parsers work with.
+    pub fn offset(&self, remaining: &str) -> usize {
+        self.source.len().saturating_sub(remaining.len())
+    }
+
+    pub fn position(&self, offset: usize) -> Position {
+        let line_starts = self
+            .line_starts
+            .get_or_init(|| span::line_starts(self.source));
+
+        span::position(self.source, line_starts, offset)
+    }
+
+    pub fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(self.position(start), self.position(end))
+    }
+
+    /// Span from the start of `input` up to the start of `rest`.
+    pub fn span_between(&self, input: &str, rest: &str) -> Span {
+        self.span(self.offset(input), self.offset(rest))
+    }
 }
 
 pub fn process_newline(input: &str) -> IResult<&str, &str> {
@@ -1,42 +1,86 @@
-use nom::{IResult, bytes::complete::take_till};
+use nom::bytes::complete::take_till;
 
 use super::{
     HsmlNode, HsmlProcessContext, RootNode,
     comment::node::{comment_dev_node, comment_native_node},
+    doctype::node::doctype_node,
+    error::{HsmlError, HsmlErrorKind, HsmlParseError},
+    raw_html::node::raw_html_node,
     tag::node::tag_node,
+    text::node::text_pipe_node,
 };
 
-pub fn parse(input: &str) -> IResult<&str, RootNode> {
+fn to_parse_error(error: nom::Err<HsmlError>, context: &HsmlProcessContext) -> HsmlParseError {
+    match error {
+        nom::Err::Error(error) | nom::Err::Failure(error) => error.into_parse_error(context),
+        nom::Err::Incomplete(_) => HsmlParseError::UnexpectedInput {
+            found: None,
+            position: context.position(context.source.len()),
+        },
+    }
+}
+
+pub fn parse(input: &str) -> Result<RootNode, HsmlParseError> {
     let mut nodes: Vec<HsmlNode> = vec![];
 
-    let mut context = HsmlProcessContext::default();
+    let mut context = HsmlProcessContext::new(input);
 
     let mut input = input;
 
     loop {
         // eat leading and trailing newlines and whitespace if there are any
-        if let Ok((rest, _)) =
-            take_till::<_, &str, nom::error::Error<&str>>(|c: char| !c.is_whitespace())(input)
-        {
-            input = rest;
+        let (rest, whitespace) =
+            take_till::<_, &str, HsmlError>(|c: char| !c.is_whitespace())(input)
+                .map_err(|err| to_parse_error(err, &context))?;
 
-            if input.is_empty() {
-                break;
-            }
+        // root nodes must not be indented
+        let line_start = whitespace.rfind('\n').map_or(0, |index| index + 1);
+        let indentation_input = &input[line_start..];
+
+        input = rest;
+
+        if input.is_empty() {
+            break;
+        }
+
+        if line_start < whitespace.len() {
+            return Err(
+                HsmlError::new(indentation_input, HsmlErrorKind::UnexpectedIndent)
+                    .into_parse_error(&context),
+            );
         }
 
-        if let Ok((rest, node)) = comment_native_node(input) {
+        if let Ok((rest, node)) = comment_native_node(input, &context) {
             nodes.push(HsmlNode::Comment(node));
             input = rest;
             continue;
         }
 
-        if let Ok((rest, node)) = comment_dev_node(input) {
+        if let Ok((rest, node)) = comment_dev_node(input, &context) {
             nodes.push(HsmlNode::Comment(node));
             input = rest;
             continue;
         }
 
+        if let Ok((rest, node)) = text_pipe_node(input, &mut context) {
+            nodes.push(HsmlNode::Text(node));
+            input = rest;
+            continue;
+        }
+
+        // a doctype is only allowed on the root level
+        if let Ok((rest, node)) = doctype_node(input, &context) {
+            nodes.push(HsmlNode::Doctype(node));
+            input = rest;
+            continue;
+        }
+
+        if let Ok((rest, node)) = raw_html_node(input, &context) {
+            nodes.push(HsmlNode::RawHtml(node));
+            input = rest;
+            continue;
+        }
+
         match tag_node(input, &mut context) {
             Ok((rest, node)) => {
                 nodes.push(HsmlNode::Tag(node));
@@ -44,23 +88,28 @@ pub fn parse(input: &str) -> IResult<&str, RootNode> {
                 continue;
             }
             Err(e) => {
-                return Err(e);
+                return Err(to_parse_error(e, &context));
             }
         }
-
-        // TODO @Shinigami92 2023-05-18: Add support for doctype node
     }
 
-    Ok((input, RootNode { nodes }))
+    Ok(RootNode { nodes })
 }
 
 #[cfg(test)]
 mod tests {
-    use nom::error::{Error, ErrorKind};
-
     use crate::parser::{
-        HsmlNode, RootNode, attribute::node::AttributeNode, class::node::ClassNode,
-        comment::node::CommentNode, parse::parse, tag::node::TagNode, text::node::TextNode,
+        HsmlNode, RootNode,
+        attribute::node::{AttributeNode, AttributeQuote},
+        class::node::ClassNode,
+        comment::node::CommentNode,
+        doctype::node::DoctypeNode,
+        error::HsmlParseError,
+        parse::parse,
+        raw_html::node::RawHtmlNode,
+        span::{Position, Span},
+        tag::node::TagNode,
+        text::node::TextNode,
     };
 
     #[test]
@@ -74,7 +123,7 @@ mod tests {
   .card__body {{ fullName }}
 "#;
 
-        let (input, root_node) = parse(input).unwrap();
+        let root_node = parse(input).unwrap();
 
         assert_eq!(
             root_node,
@@ -84,19 +133,27 @@ mod tests {
                         tag: String::from("h1"),
                         id: None,
                         classes: Some(vec![ClassNode {
-                            name: String::from("text-red")
+                            name: String::from("text-red"),
+                            span: Span::new(Position::new(2, 1, 3), Position::new(11, 1, 12)),
                         }]),
                         attributes: None,
                         text: Some(TextNode {
                             text: String::from("Vite CJS Faker Demo"),
+                            raw_text: String::from("Vite CJS Faker Demo"),
+                            is_unescaped: false,
+                            segments: None,
+                            span: Span::new(Position::new(12, 1, 13), Position::new(31, 1, 32)),
                         }),
                         children: None,
+                        is_block_expansion: false,
+                        span: Span::new(Position::new(0, 1, 1), Position::new(31, 1, 32)),
                     }),
                     HsmlNode::Tag(TagNode {
                         tag: String::from("div"),
                         id: None,
                         classes: Some(vec![ClassNode {
                             name: String::from("card"),
+                            span: Span::new(Position::new(32, 2, 1), Position::new(37, 2, 6)),
                         }]),
                         attributes: None,
                         text: None,
@@ -106,6 +163,10 @@ mod tests {
                                 id: None,
                                 classes: Some(vec![ClassNode {
                                     name: String::from("card__image"),
+                                    span: Span::new(
+                                        Position::new(40, 3, 3),
+                                        Position::new(52, 3, 15)
+                                    ),
                                 }]),
                                 attributes: None,
                                 text: None,
@@ -117,23 +178,50 @@ mod tests {
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":src"),
                                             value: Some(String::from("natureImageUrl")),
+                                            raw_value: Some(String::from("natureImageUrl")),
+                                            quote: Some(AttributeQuote::Double),
+                                            is_unescaped: false,
+                                            span: Span::new(
+                                                Position::new(61, 4, 9),
+                                                Position::new(82, 4, 30)
+                                            ),
                                         }),
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":alt"),
                                             value: Some(String::from(
                                                 "'Background image for ' + fullName"
                                             )),
+                                            raw_value: Some(String::from(
+                                                "'Background image for ' + fullName"
+                                            )),
+                                            quote: Some(AttributeQuote::Double),
+                                            is_unescaped: false,
+                                            span: Span::new(
+                                                Position::new(83, 4, 31),
+                                                Position::new(124, 4, 72)
+                                            ),
                                         }),
                                     ]),
                                     text: None,
                                     children: None,
+                                    is_block_expansion: false,
+                                    span: Span::new(
+                                        Position::new(57, 4, 5),
+                                        Position::new(125, 4, 73)
+                                    ),
                                 })]),
+                                is_block_expansion: false,
+                                span: Span::new(Position::new(40, 3, 3), Position::new(125, 4, 73)),
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("div"),
                                 id: None,
                                 classes: Some(vec![ClassNode {
                                     name: String::from("card__profile"),
+                                    span: Span::new(
+                                        Position::new(128, 5, 3),
+                                        Position::new(142, 5, 17)
+                                    ),
                                 }]),
                                 attributes: None,
                                 text: None,
@@ -145,37 +233,79 @@ mod tests {
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":src"),
                                             value: Some(String::from("avatarUrl")),
+                                            raw_value: Some(String::from("avatarUrl")),
+                                            quote: Some(AttributeQuote::Double),
+                                            is_unescaped: false,
+                                            span: Span::new(
+                                                Position::new(151, 6, 9),
+                                                Position::new(167, 6, 25)
+                                            ),
                                         }),
                                         HsmlNode::Attribute(AttributeNode {
                                             key: String::from(":alt"),
                                             value: Some(String::from(
                                                 "'Avatar image of ' + fullName"
                                             )),
+                                            raw_value: Some(String::from(
+                                                "'Avatar image of ' + fullName"
+                                            )),
+                                            quote: Some(AttributeQuote::Double),
+                                            is_unescaped: false,
+                                            span: Span::new(
+                                                Position::new(168, 6, 26),
+                                                Position::new(204, 6, 62)
+                                            ),
                                         }),
                                     ]),
                                     text: None,
                                     children: None,
+                                    is_block_expansion: false,
+                                    span: Span::new(
+                                        Position::new(147, 6, 5),
+                                        Position::new(205, 6, 63)
+                                    ),
                                 })]),
+                                is_block_expansion: false,
+                                span: Span::new(
+                                    Position::new(128, 5, 3),
+                                    Position::new(205, 6, 63)
+                                ),
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("div"),
                                 id: None,
                                 classes: Some(vec![ClassNode {
                                     name: String::from("card__body"),
+                                    span: Span::new(
+                                        Position::new(208, 7, 3),
+                                        Position::new(219, 7, 14)
+                                    ),
                                 }]),
                                 attributes: None,
                                 text: Some(TextNode {
                                     text: String::from("{{ fullName }}"),
+                                    raw_text: String::from("{{ fullName }}"),
+                                    is_unescaped: false,
+                                    segments: None,
+                                    span: Span::new(
+                                        Position::new(220, 7, 15),
+                                        Position::new(234, 7, 29)
+                                    ),
                                 }),
                                 children: None,
+                                is_block_expansion: false,
+                                span: Span::new(
+                                    Position::new(208, 7, 3),
+                                    Position::new(234, 7, 29)
+                                ),
                             })
                         ]),
+                        is_block_expansion: false,
+                        span: Span::new(Position::new(32, 2, 1), Position::new(234, 7, 29)),
                     }),
                 ],
             }
         );
-
-        assert_eq!(input, "");
     }
 
     #[test]
@@ -196,7 +326,7 @@ div
     )
 "#;
 
-        let (input, root_node) = parse(input).unwrap();
+        let root_node = parse(input).unwrap();
 
         assert_eq!(
             root_node,
@@ -205,10 +335,12 @@ div
                     HsmlNode::Comment(CommentNode {
                         text: String::from(" this is a root dev comment"),
                         is_dev: true,
+                        span: Span::new(Position::new(0, 1, 1), Position::new(29, 1, 30)),
                     }),
                     HsmlNode::Comment(CommentNode {
                         text: String::from(" this is a root native comment (will get rendered)"),
                         is_dev: false,
+                        span: Span::new(Position::new(30, 2, 1), Position::new(83, 2, 54)),
                     }),
                     HsmlNode::Tag(TagNode {
                         tag: String::from("div"),
@@ -220,6 +352,7 @@ div
                             HsmlNode::Comment(CommentNode {
                                 text: String::from(" this is a child comment"),
                                 is_dev: true,
+                                span: Span::new(Position::new(92, 4, 5), Position::new(118, 4, 31)),
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("p"),
@@ -227,13 +360,29 @@ div
                                 classes: None,
                                 attributes: None,
                                 text: Some(TextNode {
-                                    text: String::from("another tag")
+                                    text: String::from("another tag"),
+                                    raw_text: String::from("another tag"),
+                                    is_unescaped: false,
+                                    segments: None,
+                                    span: Span::new(
+                                        Position::new(125, 5, 7),
+                                        Position::new(136, 5, 18)
+                                    ),
                                 }),
                                 children: None,
+                                is_block_expansion: false,
+                                span: Span::new(
+                                    Position::new(123, 5, 5),
+                                    Position::new(136, 5, 18)
+                                ),
                             }),
                             HsmlNode::Comment(CommentNode {
                                 text: String::from(" this is a child comment that gets rendered"),
                                 is_dev: false,
+                                span: Span::new(
+                                    Position::new(141, 6, 5),
+                                    Position::new(187, 6, 51)
+                                ),
                             }),
                             HsmlNode::Tag(TagNode {
                                 tag: String::from("img"),
@@ -243,38 +392,79 @@ div
                                     HsmlNode::Comment(CommentNode {
                                         text: String::from(" supports attribute inline comments"),
                                         is_dev: true,
+                                        span: Span::new(
+                                            Position::new(205, 8, 9),
+                                            Position::new(242, 8, 46)
+                                        ),
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("src"),
                                         value: Some(String::from("/fancy-avatar.jpg")),
+                                        raw_value: Some(String::from("/fancy-avatar.jpg")),
+                                        quote: Some(AttributeQuote::Double),
+                                        is_unescaped: false,
+                                        span: Span::new(
+                                            Position::new(251, 9, 9),
+                                            Position::new(274, 9, 32)
+                                        ),
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("alt"),
                                         value: Some(String::from("Fancy Avatar")),
+                                        raw_value: Some(String::from("Fancy Avatar")),
+                                        quote: Some(AttributeQuote::Double),
+                                        is_unescaped: false,
+                                        span: Span::new(
+                                            Position::new(283, 10, 9),
+                                            Position::new(301, 10, 27)
+                                        ),
                                     }),
                                     HsmlNode::Comment(CommentNode {
                                         text: String::from(" the size of the image"),
                                         is_dev: true,
+                                        span: Span::new(
+                                            Position::new(310, 11, 9),
+                                            Position::new(334, 11, 33)
+                                        ),
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("width"),
                                         value: Some(String::from("384")),
+                                        raw_value: Some(String::from("384")),
+                                        quote: Some(AttributeQuote::Double),
+                                        is_unescaped: false,
+                                        span: Span::new(
+                                            Position::new(343, 12, 9),
+                                            Position::new(354, 12, 20)
+                                        ),
                                     }),
                                     HsmlNode::Attribute(AttributeNode {
                                         key: String::from("height"),
                                         value: Some(String::from("512")),
+                                        raw_value: Some(String::from("512")),
+                                        quote: Some(AttributeQuote::Double),
+                                        is_unescaped: false,
+                                        span: Span::new(
+                                            Position::new(363, 13, 9),
+                                            Position::new(375, 13, 21)
+                                        ),
                                     }),
                                 ]),
                                 text: None,
                                 children: None,
+                                is_block_expansion: false,
+                                span: Span::new(
+                                    Position::new(192, 7, 5),
+                                    Position::new(381, 14, 6)
+                                ),
                             }),
-                        ])
+                        ]),
+                        is_block_expansion: false,
+                        span: Span::new(Position::new(84, 3, 1), Position::new(381, 14, 6)),
                     })
                 ]
             }
         );
-
-        assert_eq!(input, "");
     }
 
     #[test]
@@ -287,7 +477,7 @@ div
 )
 "#;
 
-        let (input, root_node) = parse(input).unwrap();
+        let root_node = parse(input).unwrap();
 
         assert_eq!(
             root_node,
@@ -298,36 +488,185 @@ div
                     classes: Some(vec![
                         ClassNode {
                             name: String::from("rounded-full"),
+                            span: Span::new(Position::new(3, 1, 4), Position::new(16, 1, 17)),
                         },
                         ClassNode {
                             name: String::from("mx-auto"),
+                            span: Span::new(Position::new(16, 1, 17), Position::new(24, 1, 25)),
                         },
                     ]),
                     attributes: Some(vec![
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("src"),
                             value: Some(String::from("/fancy-avatar.jpg")),
+                            raw_value: Some(String::from("/fancy-avatar.jpg")),
+                            quote: Some(AttributeQuote::Double),
+                            is_unescaped: false,
+                            span: Span::new(Position::new(30, 2, 5), Position::new(53, 2, 28)),
                         }),
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("alt"),
                             value: Some(String::from("A fancy avatar")),
+                            raw_value: Some(String::from("A fancy avatar")),
+                            quote: Some(AttributeQuote::Double),
+                            is_unescaped: false,
+                            span: Span::new(Position::new(58, 3, 5), Position::new(78, 3, 25)),
                         }),
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("width"),
                             value: Some(String::from("384")),
+                            raw_value: Some(String::from("384")),
+                            quote: Some(AttributeQuote::Double),
+                            is_unescaped: false,
+                            span: Span::new(Position::new(83, 4, 5), Position::new(94, 4, 16)),
                         }),
                         HsmlNode::Attribute(AttributeNode {
                             key: String::from("height"),
                             value: Some(String::from("512")),
+                            raw_value: Some(String::from("512")),
+                            quote: Some(AttributeQuote::Double),
+                            is_unescaped: false,
+                            span: Span::new(Position::new(99, 5, 5), Position::new(111, 5, 17)),
                         }),
                     ]),
                     text: None,
                     children: None,
+                    is_block_expansion: false,
+                    span: Span::new(Position::new(0, 1, 1), Position::new(113, 6, 2)),
                 })],
             }
         );
+    }
+
+    #[test]
+    fn it_should_parse_children_separated_by_whitespace_only_lines() {
+        let input = "div\n  p\n  \n\n  span\n";
+
+        let root_node = parse(input).unwrap();
+
+        let HsmlNode::Tag(div) = &root_node.nodes[0] else {
+            panic!("expected a tag node");
+        };
+
+        assert_eq!(root_node.nodes.len(), 1);
+        assert_eq!(div.children.as_ref().map(Vec::len), Some(2));
+    }
+
+    #[test]
+    fn it_should_parse_doctype() {
+        let input = "doctype html\nhtml\n";
+
+        let root_node = parse(input).unwrap();
+
+        assert_eq!(
+            root_node.nodes[0],
+            HsmlNode::Doctype(DoctypeNode {
+                value: String::from("html"),
+                span: Span::new(Position::new(0, 1, 1), Position::new(12, 1, 13)),
+            })
+        );
+        assert!(matches!(&root_node.nodes[1], HsmlNode::Tag(tag) if tag.tag == "html"));
+    }
+
+    #[test]
+    fn it_should_parse_raw_html() {
+        let input = "<div id=\"embed\"></div>\nbody\n  <\n    <br>\n  p\n";
+
+        let root_node = parse(input).unwrap();
+
+        assert_eq!(
+            root_node.nodes[0],
+            HsmlNode::RawHtml(RawHtmlNode {
+                html: String::from("<div id=\"embed\"></div>"),
+                span: Span::new(Position::new(0, 1, 1), Position::new(22, 1, 23)),
+            })
+        );
+
+        let HsmlNode::Tag(body) = &root_node.nodes[1] else {
+            panic!("expected a tag node");
+        };
+        assert!(matches!(
+            body.children.as_deref(),
+            Some([HsmlNode::RawHtml(raw_html), HsmlNode::Tag(p)]) if raw_html.html == "<br>" && p.tag == "p"
+        ));
+    }
+
+    #[test]
+    fn it_should_parse_pipe_text_between_children() {
+        let input = "p Hello\n  strong world\n  |  and more\n| root text\n";
+
+        let root_node = parse(input).unwrap();
+
+        let HsmlNode::Tag(p) = &root_node.nodes[0] else {
+            panic!("expected a tag node");
+        };
+
+        assert_eq!(
+            p.text.as_ref().map(|text| text.text.as_str()),
+            Some("Hello")
+        );
+        assert!(matches!(&p.children.as_ref().unwrap()[..], [
+            HsmlNode::Tag(strong),
+            HsmlNode::Text(text),
+        ] if strong.tag == "strong" && text.text == " and more"));
+        assert_eq!(
+            root_node.nodes[1],
+            HsmlNode::Text(TextNode {
+                text: String::from("root text"),
+                raw_text: String::from("root text"),
+                is_unescaped: false,
+                segments: None,
+                span: Span::new(Position::new(37, 4, 1), Position::new(48, 4, 12)),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_parse_spans_of_multibyte_characters() {
+        let input = "p.größe(alt=\"🤷\") Grüße 👋\n";
+
+        let root_node = parse(input).unwrap();
+
+        let HsmlNode::Tag(p) = &root_node.nodes[0] else {
+            panic!("expected a tag node");
+        };
+
+        assert_eq!(
+            p.classes.as_ref().unwrap()[0].span,
+            Span::new(Position::new(1, 1, 2), Position::new(9, 1, 8))
+        );
+        assert_eq!(
+            p.attributes.as_ref().unwrap()[0].span(),
+            Some(Span::new(Position::new(10, 1, 9), Position::new(20, 1, 16)))
+        );
+        assert_eq!(
+            p.text.as_ref().unwrap().span,
+            Span::new(Position::new(22, 1, 18), Position::new(34, 1, 25))
+        );
+        assert_eq!(
+            p.span,
+            Span::new(Position::new(0, 1, 1), Position::new(34, 1, 25))
+        );
+    }
 
-        assert_eq!(input, "");
+    #[test]
+    fn it_should_parse_without_trailing_newline() {
+        let input = "div\n  p Hello\n  // last line";
+
+        let root_node = parse(input).unwrap();
+
+        let HsmlNode::Tag(div) = &root_node.nodes[0] else {
+            panic!("expected a tag node");
+        };
+
+        assert_eq!(
+            div.children.as_ref().unwrap()[1],
+            HsmlNode::Comment(CommentNode {
+                text: String::from(" last line"),
+                is_dev: true,
+                span: Span::new(Position::new(16, 3, 3), Position::new(28, 3, 15)),
+            })
+        );
     }
 
     // Negative tests
@@ -337,10 +676,103 @@ div
         let input = r#"div#id1#id2"#;
 
         assert_eq!(
-            Err(nom::Err::Failure(Error {
-                input: "#id2",
-                code: ErrorKind::Tag
-            })),
+            Err(HsmlParseError::DuplicateId {
+                id: String::from("id2"),
+                position: Position::new(7, 1, 8),
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_mixed_indentation() {
+        let input = "div\n \tp\n";
+
+        assert_eq!(
+            Err(HsmlParseError::MixedIndentation {
+                position: Position::new(4, 2, 1),
+            }),
+            parse(input)
+        );
+
+        let input = "div\n\tp\n\t\tspan\nsection\n  p\n";
+
+        assert_eq!(
+            Err(HsmlParseError::MixedIndentation {
+                position: Position::new(22, 5, 1),
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_unclosed_attribute_list() {
+        let input = "img(src=\"a.png\"\n";
+
+        assert_eq!(
+            Err(HsmlParseError::UnclosedAttributeList {
+                position: Position::new(3, 1, 4),
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_unterminated_quote() {
+        let input = "img(src=\"a.png)\n";
+
+        assert_eq!(
+            Err(HsmlParseError::UnterminatedQuote {
+                quote: '"',
+                position: Position::new(8, 1, 9),
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_invalid_tag_name() {
+        let input = "div\n  42.input\n";
+
+        assert_eq!(
+            Err(HsmlParseError::InvalidTagName {
+                name: String::from("42"),
+                position: Position::new(6, 2, 3),
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_unexpected_indent() {
+        let input = "div\n  p\n      span\n";
+
+        assert_eq!(
+            Err(HsmlParseError::UnexpectedIndent {
+                position: Position::new(8, 3, 1),
+            }),
+            parse(input)
+        );
+
+        let input = "// comment\n  div\n";
+
+        assert_eq!(
+            Err(HsmlParseError::UnexpectedIndent {
+                position: Position::new(11, 2, 1),
+            }),
+            parse(input)
+        );
+    }
+
+    #[test]
+    fn it_should_not_parse_invalid_attribute_key() {
+        let input = "div(1src=\"x\")\n";
+
+        assert_eq!(
+            Err(HsmlParseError::UnexpectedInput {
+                found: Some('1'),
+                position: Position::new(4, 1, 5),
+            }),
             parse(input)
         );
     }
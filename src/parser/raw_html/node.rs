@@ -0,0 +1,121 @@
+use serde::Serialize;
+
+use crate::parser::{HsmlProcessContext, error::HsmlResult, span::Span};
+
+use super::process::{process_raw_html, process_raw_html_block};
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename = "rawHtml", rename_all = "camelCase")]
+pub struct RawHtmlNode {
+    /// Emitted as is, a block loses only the indentation all of its lines share
+    pub html: String,
+    pub span: Span,
+}
+
+/// Remove the indentation all non blank lines share, blank lines become empty.
+///
+/// Only spaces and tabs count as indentation, other whitespace is kept as content.
+fn dedent(block: &str) -> String {
+    let indentation = block
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    block
+        .lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ""
+            } else {
+                &line[indentation..]
+            }
+        })
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+/// Either a single line starting with `<` or a bare `<` line followed by an indented block.
+pub fn raw_html_node<'a>(
+    input: &'a str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, RawHtmlNode> {
+    let indentation = context.indent_string.as_ref().map_or(0, String::len) * context.indent_level;
+
+    if let Ok((rest, block)) = process_raw_html_block(input, indentation) {
+        return Ok((
+            rest,
+            RawHtmlNode {
+                html: dedent(block),
+                span: context.span_between(input, rest),
+            },
+        ));
+    }
+
+    let (rest, html) = process_raw_html(input).map_err(nom::Err::convert)?;
+
+    Ok((
+        rest,
+        RawHtmlNode {
+            html: html.to_string(),
+            span: context.span_between(input, rest),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{
+        HsmlProcessContext,
+        raw_html::node::{RawHtmlNode, raw_html_node},
+        span::{Position, Span},
+    };
+
+    #[test]
+    fn it_should_return_raw_html_node() {
+        let input = "<img src=\"pixel.gif\" alt=\"\">\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, raw_html) = raw_html_node(input, &context).unwrap();
+
+        assert_eq!(
+            raw_html,
+            RawHtmlNode {
+                html: String::from("<img src=\"pixel.gif\" alt=\"\">"),
+                span: Span::new(Position::new(0, 1, 1), Position::new(28, 1, 29)),
+            }
+        );
+
+        assert_eq!(input, "\n");
+    }
+
+    #[test]
+    fn it_should_return_raw_html_block_node() {
+        let input = "<\n  <script>\n    track();\n\n  </script>\np\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, raw_html) = raw_html_node(input, &context).unwrap();
+
+        assert_eq!(
+            raw_html,
+            RawHtmlNode {
+                html: String::from("<script>\n  track();\n\n</script>"),
+                span: Span::new(Position::new(0, 1, 1), Position::new(38, 5, 12)),
+            }
+        );
+
+        assert_eq!(input, "\np\n");
+    }
+
+    #[test]
+    fn it_should_return_raw_html_block_node_with_multibyte_whitespace() {
+        let input = "<\n  <i>\n\u{3000}<b>\n";
+        let context = HsmlProcessContext::new(input);
+
+        let (input, raw_html) = raw_html_node(input, &context).unwrap();
+
+        assert_eq!(raw_html.html, "  <i>\n\u{3000}<b>");
+        assert_eq!(input, "\n");
+    }
+}
@@ -0,0 +1,96 @@
+use nom::{
+    IResult, Parser,
+    branch::alt,
+    bytes::complete::{tag, take_till},
+    combinator::recognize,
+};
+
+/// Process a line starting with `<`, the whole line is raw HTML.
+pub fn process_raw_html(input: &str) -> IResult<&str, &str> {
+    recognize((tag("<"), take_till(|c: char| c == '\r' || c == '\n'))).parse(input)
+}
+
+/// Process a bare `<` line followed by a block of lines indented deeper than `indentation`.
+///
+/// Blank lines inside of the block belong to it, the block ends before the line ending of its last line.
+pub fn process_raw_html_block(input: &str, indentation: usize) -> IResult<&str, &str> {
+    let (input, _) = tag("<")(input)?;
+    let (content, _) = alt((tag("\r\n"), tag("\n"))).parse(input)?;
+
+    let mut block_end = 0;
+    let mut line_start = 0;
+
+    for line in content.split_inclusive('\n') {
+        let line_content = line.trim_end_matches(['\r', '\n']);
+
+        if !line_content.trim().is_empty() {
+            let line_indentation = line_content.len() - line_content.trim_start().len();
+
+            if line_indentation <= indentation {
+                break;
+            }
+
+            block_end = line_start + line_content.len();
+        }
+
+        line_start += line.len();
+    }
+
+    if block_end == 0 {
+        // an empty block, the line ending stays for the next node
+        return Ok((input, ""));
+    }
+
+    Ok((&content[block_end..], &content[..block_end]))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::{Error, ErrorKind};
+
+    use crate::parser::raw_html::process::{process_raw_html, process_raw_html_block};
+
+    #[test]
+    fn it_should_process_raw_html() {
+        let input = "<img src=\"pixel.gif\">\r\np";
+
+        let (rest, html) = process_raw_html(input).unwrap();
+
+        assert_eq!(html, "<img src=\"pixel.gif\">");
+        assert_eq!(rest, "\r\np");
+    }
+
+    #[test]
+    fn it_should_process_raw_html_block() {
+        let input = "<\n    <div>\n\n      <b>embed</b>\n    </div>\n\n  p\n";
+
+        let (rest, html) = process_raw_html_block(input, 2).unwrap();
+
+        assert_eq!(html, "    <div>\n\n      <b>embed</b>\n    </div>");
+        assert_eq!(rest, "\n\n  p\n");
+    }
+
+    #[test]
+    fn it_should_process_empty_raw_html_block() {
+        let input = "<\np\n";
+
+        let (rest, html) = process_raw_html_block(input, 0).unwrap();
+
+        assert_eq!(html, "");
+        assert_eq!(rest, "\np\n");
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_process_raw_html_without_angle_bracket() {
+        let input = "div <b>\n";
+
+        let result = process_raw_html(input);
+
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use serde::Serialize;
+
+/// A location inside the source.
+///
+/// `offset` is a zero-based byte offset, `line` and `column` are one-based and the column is
+/// counted in characters. The JavaScript export counts both in UTF-16 code units instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub const fn new(offset: usize, line: usize, column: usize) -> Self {
+        Self {
+            offset,
+            line,
+            column,
+        }
+    }
+}
+
+/// The range of the source a node was parsed from.
+///
+/// `start` is inclusive, `end` is exclusive.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub const fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+}
+
+/// Byte offsets of the first character of every line in `source`.
+pub(crate) fn line_starts(source: &str) -> Vec<usize> {
+    let mut line_starts = vec![0];
+
+    line_starts.extend(
+        source
+            .bytes()
+            .enumerate()
+            .filter(|&(_, b)| b == b'\n')
+            .map(|(index, _)| index + 1),
+    );
+
+    line_starts
+}
+
+/// Resolve a byte offset into a [`Position`] using the precomputed `line_starts` of `source`.
+pub(crate) fn position(source: &str, line_starts: &[usize], offset: usize) -> Position {
+    let offset = offset.min(source.len());
+
+    // `line_starts` always contains `0`, so the partition point is at least `1`
+    let line_index = line_starts
+        .partition_point(|&line_start| line_start <= offset)
+        .saturating_sub(1);
+    let line_start = line_starts.get(line_index).copied().unwrap_or(0);
+
+    let column = source
+        .get(line_start..offset)
+        .map(|line| line.chars().count())
+        .unwrap_or(offset - line_start);
+
+    Position {
+        offset,
+        line: line_index + 1,
+        column: column + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::span::{Position, line_starts, position};
+
+    #[test]
+    fn it_should_resolve_position() {
+        let source = "div\n  p Hello\n";
+        let line_starts = line_starts(source);
+
+        assert_eq!(line_starts, vec![0, 4, 14]);
+        assert_eq!(position(source, &line_starts, 0), Position::new(0, 1, 1));
+        assert_eq!(position(source, &line_starts, 3), Position::new(3, 1, 4));
+        assert_eq!(position(source, &line_starts, 6), Position::new(6, 2, 3));
+        assert_eq!(position(source, &line_starts, 14), Position::new(14, 3, 1));
+    }
+
+    #[test]
+    fn it_should_count_columns_in_characters() {
+        let source = "p 🤷 ok";
+        let line_starts = line_starts(source);
+
+        assert_eq!(position(source, &line_starts, 7), Position::new(7, 1, 5));
+    }
+}
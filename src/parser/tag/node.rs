@@ -1,19 +1,23 @@
 use nom::{
-    IResult,
-    bytes::complete::{take_till, take_till1},
-    error::{Error, ErrorKind},
+    Parser,
+    bytes::complete::{tag, take_till},
 };
+use serde::Serialize;
 
 use crate::parser::{
     HsmlNode, HsmlProcessContext, attribute,
     class::node::{ClassNode, class_node},
     comment::node::{comment_dev_node, comment_native_node},
+    error::{HsmlError, HsmlErrorKind, HsmlResult},
     id::{self, node::IdNode},
+    raw_html::node::raw_html_node,
+    span::Span,
     tag::process::process_tag,
     text::{self, node::TextNode},
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "tag", rename_all = "camelCase")]
 pub struct TagNode {
     pub tag: String,
     pub id: Option<IdNode>,
@@ -21,16 +25,127 @@ pub struct TagNode {
     pub attributes: Option<Vec<HsmlNode>>,
     pub text: Option<TextNode>,
     pub children: Option<Vec<HsmlNode>>,
+    /// Written as `parent: child`, the single child tag follows on the same line
+    pub is_block_expansion: bool,
+    pub span: Span,
 }
 
-pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult<&'a str, TagNode> {
+/// Elements whose text is neither interpolated nor escaped.
+///
+/// See https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements
+pub const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// A `.` at the end of the line starts a text block.
+fn is_text_block_start(input: &str) -> bool {
+    input == "." || input.starts_with(".\n") || input.starts_with(".\r\n")
+}
+
+fn invalid_tag_name(input: &str) -> nom::Err<HsmlError<'_>> {
+    // report everything up to the next delimiter as the invalid tag name
+    let name_end = input
+        .find(|c: char| c.is_whitespace() || c == '.' || c == '#' || c == '(')
+        .unwrap_or(input.len());
+
+    let name = match &input[..name_end] {
+        "" => input.chars().next().map(String::from).unwrap_or_default(),
+        name => name.to_string(),
+    };
+
+    nom::Err::Failure(HsmlError::new(input, HsmlErrorKind::InvalidTagName(name)))
+}
+
+/// A tag inside of text, written as `#[tag#id.class(attributes) text]`.
+pub fn interpolated_tag_node<'a>(
+    input: &'a str,
+    context: &mut HsmlProcessContext,
+) -> HsmlResult<'a, TagNode> {
+    let tag_start = input;
+
+    let unclosed_interpolation = || {
+        nom::Err::Failure(HsmlError::new(
+            tag_start,
+            HsmlErrorKind::UnclosedInterpolation,
+        ))
+    };
+
+    let (input, _) = tag("#[").parse(input)?;
+
+    let (mut input, tag_name) = if input.starts_with('.') || input.starts_with('#') {
+        (input, "div")
+    } else {
+        process_tag(input).map_err(|_| invalid_tag_name(input))?
+    };
+
+    let mut id_node: Option<IdNode> = None;
+    let mut class_nodes: Vec<ClassNode> = vec![];
+    let mut attribute_nodes: Option<Vec<HsmlNode>> = None;
+    let mut text_node: Option<TextNode> = None;
+
+    loop {
+        if input.starts_with('#') {
+            let (rest, node) = id::node::id_node(input, context, true)?;
+
+            if id_node.is_some() {
+                return Err(nom::Err::Failure(HsmlError::new(
+                    input,
+                    HsmlErrorKind::DuplicateId(node.id),
+                )));
+            }
+
+            id_node = Some(node);
+            input = rest;
+        } else if input.starts_with('.') {
+            let (rest, node) = class_node(input, context, true)?;
+            class_nodes.push(node);
+            input = rest;
+        } else if input.starts_with('(') {
+            let (rest, nodes) = attribute::node::attribute_nodes(input, context)?;
+            attribute_nodes.get_or_insert_with(Vec::new).extend(nodes);
+            input = rest;
+        } else {
+            break;
+        }
+    }
+
+    if input.starts_with(' ') {
+        let (rest, node) =
+            text::node::interpolated_text_node(input, context).map_err(|err| match err {
+                nom::Err::Error(_) => unclosed_interpolation(),
+                err => err,
+            })?;
+        text_node = Some(node);
+        input = rest;
+    }
+
+    let (input, _) = tag::<_, _, HsmlError>("]")
+        .parse(input)
+        .map_err(|_| unclosed_interpolation())?;
+
+    Ok((
+        input,
+        TagNode {
+            tag: tag_name.to_string(),
+            id: id_node,
+            classes: (!class_nodes.is_empty()).then_some(class_nodes),
+            attributes: attribute_nodes,
+            text: text_node,
+            children: None,
+            is_block_expansion: false,
+            span: context.span_between(tag_start, input),
+        },
+    ))
+}
+
+pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> HsmlResult<'a, TagNode> {
+    let tag_start = input;
+
     // tag node starts with a tag name or a dot/hash
     // if it starts with a dot/hash, the tag name is div
 
     let (mut input, tag_name) = if input.starts_with('.') || input.starts_with('#') {
         (input, "div")
     } else {
-        process_tag(input)?
+        process_tag(input).map_err(|_| invalid_tag_name(input))?
     };
 
     // if the next char is a dot, we have a id node
@@ -42,6 +157,9 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
     let mut attribute_nodes: Option<Vec<HsmlNode>> = None;
     let mut text_node: Option<TextNode> = None;
     let mut child_nodes: Vec<HsmlNode> = vec![];
+    let mut is_block_expansion = false;
+
+    let is_raw_text = RAW_TEXT_ELEMENTS.contains(&tag_name);
 
     loop {
         let first_char = input.get(..1);
@@ -50,24 +168,48 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
         if first_char == Some("#") {
             // we hit an id node
 
+            let (rest, node) = id::node::id_node(input, context, false)?;
+
             // if there was already an id node, throw an error
             if id_node.is_some() {
-                // TODO @Shinigami92 2023-05-25: This error could be more specific
-                // Duplicate attribute "id" is not allowed.
-                return Err(nom::Err::Failure(Error::new(input, ErrorKind::Tag)));
+                return Err(nom::Err::Failure(HsmlError::new(
+                    input,
+                    HsmlErrorKind::DuplicateId(node.id),
+                )));
             }
 
-            let (rest, node) = id::node::id_node(input)?;
             id_node = Some(node);
             input = rest;
 
             continue;
         }
 
+        if first_two_chars == Some("!=") {
+            // we hit unescaped text, either inline or as text block
+            let rest = &input[2..];
+
+            let (rest, mut node) = match (is_text_block_start(rest), is_raw_text) {
+                (true, true) => text::node::raw_text_block_node(rest, tag_name, context)?,
+                (true, false) => text::node::text_block_node(rest, context)?,
+                (false, true) => text::node::raw_text_node(rest, tag_name, context)?,
+                (false, false) => text::node::text_node(rest, context)?,
+            };
+
+            node.is_unescaped = true;
+            text_node = Some(node);
+            input = rest;
+
+            break;
+        }
+
         if first_char == Some(".") {
-            if first_two_chars == Some(".\n") {
+            if is_text_block_start(input) {
                 // we hit piped text
-                let (rest, node) = text::node::text_block_node(input, context)?;
+                let (rest, node) = if is_raw_text {
+                    text::node::raw_text_block_node(input, tag_name, context)?
+                } else {
+                    text::node::text_block_node(input, context)?
+                };
                 text_node = Some(node);
                 input = rest;
 
@@ -75,7 +217,7 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
             }
 
             // we hit a class node
-            let (rest, node) = class_node(input)?;
+            let (rest, node) = class_node(input, context, false)?;
             class_nodes.push(node);
             input = rest;
 
@@ -83,19 +225,35 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
         }
 
         if first_char == Some("(") {
-            // we hit the start of attributes
+            // we hit the start of attributes, multiple groups are merged in order
 
             let (rest, nodes) = attribute::node::attribute_nodes(input, context)?;
-            attribute_nodes = Some(nodes);
+            attribute_nodes.get_or_insert_with(Vec::new).extend(nodes);
             input = rest;
 
             continue;
         }
 
+        if first_two_chars == Some(": ") {
+            // we hit a block expansion, the rest of the line is the single child tag node
+            // nested lines are handled by the child, because it shares our indentation level
+
+            let (rest, node) = tag_node(&input[2..], context)?;
+            child_nodes.push(HsmlNode::Tag(node));
+            is_block_expansion = true;
+            input = rest;
+
+            break;
+        }
+
         if first_char == Some(" ") {
             // we hit a whitespace and there should be text
 
-            let (rest, node) = text::node::text_node(input)?;
+            let (rest, node) = if is_raw_text {
+                text::node::raw_text_node(input, tag_name, context)?
+            } else {
+                text::node::text_node(input, context)?
+            };
             text_node = Some(node);
             input = rest;
 
@@ -103,80 +261,91 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
 
             // there could be child tag nodes, but this will be handled in the next loop iteration by the line ending check
 
-            break;
+            continue;
         }
 
         if first_char == Some("\n") || first_two_chars == Some("\r\n") {
             // we hit a newline and the tag ended but could have child tag nodes
 
-            // check indentation
-            let (rest, _) = take_till1(|c| c != '\r' && c != '\n')(input)?;
-
-            // check if the next char is a tab or whitespace
-            // if yes, check for indentation level
-            // if no, we have no child tag nodes and can break the loop
-
-            let (remaining, indentation) = take_till(|c: char| !c.is_whitespace())(rest)?;
-
-            if !indentation.is_empty() {
-                // check that the indentation is consistent and does not include tabs and spaces at the same time
-                // if it does, throw an error
-
-                if indentation.contains('\t') && indentation.contains(' ') {
-                    // TODO @Shinigami92 2023-05-18: This error could be more specific
-                    return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
-                }
-
-                // if we never hit an indentation yet, set it
-                // this only happens once
-                if context.indent_string.is_none() {
-                    // println!("set indent string = \"{}\"", indentation);
-                    context.indent_string = Some(indentation.to_string());
-                }
-
-                // persist the indentation level so we can restore it later
-                let indentation_level = context.indent_level;
-
-                context.indent_level += 1;
-
-                // check that we are at the correct indentation level, otherwise break out of the loop
-                let indent_string_len = context.indent_string.as_ref().unwrap().len();
-                let indent_size = indent_string_len * context.indent_level;
-                // dbg!(indent_size, indentation.len());
-                if indent_size != indentation.len() {
-                    // dbg!("break out of loop");
-                    break;
-                }
-
-                // we are at the correct indentation level, so we can continue parsing the child tag nodes
-
-                // there could be a comment (dev or native) node
-                if let Ok((rest, node)) = comment_native_node(remaining) {
-                    child_nodes.push(HsmlNode::Comment(node));
-                    input = rest;
-                } else if let Ok((rest, node)) = comment_dev_node(remaining) {
-                    child_nodes.push(HsmlNode::Comment(node));
-                    input = rest;
-                }
-                // or we have now a child tag node
-                else {
-                    // now we have a child tag node
-                    if let Ok((rest, node)) = tag_node(remaining, context) {
-                        child_nodes.push(HsmlNode::Tag(node));
-                        input = rest;
-                    } else {
-                        return Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)));
-                    }
-                }
-
-                // restore the indentation level
-                context.indent_level = indentation_level;
-
-                continue;
+            // skip blank lines, the indentation is the whitespace after the last line ending
+            let (remaining, whitespace) = take_till(|c: char| !c.is_whitespace())(input)?;
+            let line_start = whitespace.rfind('\n').map_or(0, |index| index + 1);
+            let indentation_input = &input[line_start..];
+            let indentation = &whitespace[line_start..];
+
+            // if there is no indentation, we have no child tag nodes and can break the loop
+            if indentation.is_empty() || remaining.is_empty() {
+                break;
             }
 
-            // we have no child tag nodes
-            break;
+            // if we never hit an indentation yet, set it
+            // this only happens once
+            let indent_string = context
+                .indent_string
+                .get_or_insert_with(|| indentation.to_string());
+
+            // check that the indentation is consistent and does not include tabs and spaces at the same time
+            // if it does, throw an error
+            let indent_char = indent_string.chars().next();
+            if indentation.chars().any(|c| Some(c) != indent_char) {
+                return Err(nom::Err::Failure(HsmlError::new(
+                    indentation_input,
+                    HsmlErrorKind::MixedIndentation,
+                )));
+            }
+
+            // check that we are at the correct indentation level
+            let indent_string_len = indent_string.len();
+            let indent_size = indent_string_len * (context.indent_level + 1);
+
+            if indentation.len() > indent_size || indentation.len() % indent_string_len != 0 {
+                return Err(nom::Err::Failure(HsmlError::new(
+                    indentation_input,
+                    HsmlErrorKind::UnexpectedIndent,
+                )));
+            }
+
+            if indentation.len() < indent_size {
+                // the next line belongs to one of our ancestors, so break out of the loop
+                break;
+            }
+
+            // we are at the correct indentation level, so we can continue parsing the child tag nodes
+
+            // persist the indentation level so we can restore it later
+            let indentation_level = context.indent_level;
+
+            context.indent_level += 1;
+
+            // there could be a comment (dev or native) node
+            if let Ok((rest, node)) = comment_native_node(remaining, context) {
+                child_nodes.push(HsmlNode::Comment(node));
+                input = rest;
+            } else if let Ok((rest, node)) = comment_dev_node(remaining, context) {
+                child_nodes.push(HsmlNode::Comment(node));
+                input = rest;
+            }
+            // or a `| text` line
+            else if let Ok((rest, node)) = text::node::text_pipe_node(remaining, context) {
+                child_nodes.push(HsmlNode::Text(node));
+                input = rest;
+            }
+            // or a raw HTML line or block
+            else if let Ok((rest, node)) = raw_html_node(remaining, context) {
+                child_nodes.push(HsmlNode::RawHtml(node));
+                input = rest;
+            }
+            // or we have now a child tag node
+            else {
+                let (rest, node) = tag_node(remaining, context)?;
+                child_nodes.push(HsmlNode::Tag(node));
+                input = rest;
+            }
+
+            // restore the indentation level
+            context.indent_level = indentation_level;
+
+            continue;
         }
 
         break;
@@ -191,6 +360,8 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
             attributes: attribute_nodes,
             text: text_node,
             children: (!child_nodes.is_empty()).then_some(child_nodes),
+            is_block_expansion,
+            span: context.span_between(tag_start, input),
         },
     ))
 }
@@ -198,28 +369,30 @@ pub fn tag_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> IResult
 #[cfg(test)]
 mod tests {
     use crate::parser::{
-        HsmlProcessContext,
+        HsmlNode, HsmlProcessContext,
         class::node::ClassNode,
-        tag::node::{TagNode, tag_node},
+        error::{HsmlError, HsmlErrorKind},
+        span::{Position, Span},
+        tag::node::{TagNode, interpolated_tag_node, tag_node},
         text::node::TextNode,
     };
 
     #[test]
     fn it_should_return_tag_node_with_piped_text() {
+        let input = r#"p.text-lg.font-medium.
+        "Tailwind CSS is the only framework that I've seen scale
+        on large teams. It's easy to customize, adapts to any design,
+        and the build size is tiny."
+    figcaption.font-medium"#;
+
         let context = &mut HsmlProcessContext {
+            source: input,
             indent_level: 3,
             indent_string: Some(String::from("  ")),
+            ..Default::default()
         };
 
-        let (input, tag) = tag_node(
-            r#"p.text-lg.font-medium.
-        "Tailwind CSS is the only framework that I've seen scale
-        on large teams. It's easy to customize, adapts to any design,
-        and the build size is tiny."
-    figcaption.font-medium"#,
-            context,
-        )
-        .unwrap();
+        let (input, tag) = tag_node(input, context).unwrap();
 
         assert_eq!(
             tag,
@@ -229,9 +402,11 @@ mod tests {
                 classes: Some(vec![
                     ClassNode {
                         name: String::from("text-lg"),
+                        span: Span::new(Position::new(1, 1, 2), Position::new(9, 1, 10)),
                     },
                     ClassNode {
                         name: String::from("font-medium"),
+                        span: Span::new(Position::new(9, 1, 10), Position::new(21, 1, 22)),
                     },
                 ]),
                 attributes: None,
@@ -241,11 +416,242 @@ mod tests {
 on large teams. It's easy to customize, adapts to any design,
 and the build size is tiny.""#
                     ),
+                    raw_text: String::from(
+                        r#""Tailwind CSS is the only framework that I've seen scale
+on large teams. It's easy to customize, adapts to any design,
+and the build size is tiny.""#
+                    ),
+                    is_unescaped: false,
+                    segments: None,
+                    span: Span::new(Position::new(23, 2, 1), Position::new(194, 4, 37)),
                 }),
                 children: None,
+                is_block_expansion: false,
+                span: Span::new(Position::new(0, 1, 1), Position::new(194, 4, 37)),
             }
         );
 
         assert_eq!(input, "\n    figcaption.font-medium");
     }
+
+    #[test]
+    fn it_should_return_tag_node_with_unescaped_text() {
+        let input = "p.lead!= <b>raw</b>\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (input, tag) = tag_node(input, context).unwrap();
+
+        assert_eq!(
+            tag.classes.unwrap()[0].name,
+            String::from("lead"),
+            "the class ends before `!=`"
+        );
+        assert_eq!(
+            tag.text,
+            Some(TextNode {
+                text: String::from("<b>raw</b>"),
+                raw_text: String::from("<b>raw</b>"),
+                is_unescaped: true,
+                segments: None,
+                span: Span::new(Position::new(9, 1, 10), Position::new(19, 1, 20)),
+            })
+        );
+
+        assert_eq!(input, "\n");
+    }
+
+    #[test]
+    fn it_should_return_tag_node_with_unescaped_text_block() {
+        let input = "div\n  script!=.\n    if (a < b) {}\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (_, tag) = tag_node(input, context).unwrap();
+
+        let HsmlNode::Tag(child) = &tag.children.unwrap()[0] else {
+            panic!("expected a tag node");
+        };
+
+        assert_eq!(
+            child.text,
+            Some(TextNode {
+                text: String::from("if (a < b) {}"),
+                raw_text: String::from("if (a < b) {}"),
+                is_unescaped: true,
+                segments: None,
+                span: Span::new(Position::new(16, 3, 1), Position::new(33, 3, 18)),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_return_interpolated_tag_node() {
+        let input = "#[a.link(href=\"/\") Home] page\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (input, tag) = interpolated_tag_node(input, context).unwrap();
+
+        assert_eq!(tag.tag, "a");
+        assert_eq!(tag.classes.unwrap()[0].name, "link");
+        assert_eq!(tag.attributes.unwrap().len(), 1);
+        assert_eq!(
+            tag.text,
+            Some(TextNode {
+                text: String::from("Home"),
+                raw_text: String::from("Home"),
+                is_unescaped: false,
+                segments: None,
+                span: Span::new(Position::new(19, 1, 20), Position::new(23, 1, 24)),
+            })
+        );
+        assert_eq!(
+            tag.span,
+            Span::new(Position::new(0, 1, 1), Position::new(24, 1, 25))
+        );
+
+        assert_eq!(input, " page\n");
+    }
+
+    #[test]
+    fn it_should_return_tag_node_with_interpolated_text() {
+        let input = "p Hello #[strong.red world]!\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (_, tag) = tag_node(input, context).unwrap();
+
+        let text_node = tag.text.unwrap();
+        assert_eq!(text_node.text, "Hello #[strong.red world]!");
+
+        let segments = text_node.segments.unwrap();
+        assert_eq!(segments.len(), 3);
+        assert!(matches!(&segments[0], HsmlNode::Text(text) if text.text == "Hello "));
+        assert!(matches!(&segments[1], HsmlNode::Tag(tag) if tag.tag == "strong"));
+        assert!(matches!(&segments[2], HsmlNode::Text(text) if text.text == "!"));
+    }
+
+    #[test]
+    fn it_should_return_tag_node_with_interpolated_text_block() {
+        let input = "p.\n  first #[em line]\n  #[b second] line\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (_, tag) = tag_node(input, context).unwrap();
+
+        let segments = tag.text.unwrap().segments.unwrap();
+        let texts: Vec<&str> = segments
+            .iter()
+            .map(|segment| match segment {
+                HsmlNode::Text(text) => text.text.as_str(),
+                HsmlNode::Tag(tag) => tag.tag.as_str(),
+                _ => panic!("unexpected segment"),
+            })
+            .collect();
+
+        assert_eq!(texts, vec!["first ", "em", "\n", "b", " line"]);
+        assert_eq!(segments[3].span().unwrap().start.line, 3);
+    }
+
+    #[test]
+    fn it_should_return_tag_node_with_block_expansion() {
+        let input = "ul\n  li.item: a(href=\"/\"): span Home\n    | nested\n  li Last\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (_, tag) = tag_node(input, context).unwrap();
+
+        let children = tag.children.unwrap();
+        assert_eq!(children.len(), 2);
+
+        let HsmlNode::Tag(li) = &children[0] else {
+            panic!("expected a tag node");
+        };
+        assert!(li.is_block_expansion);
+        assert_eq!(li.classes.as_ref().unwrap()[0].name, "item");
+
+        let Some([HsmlNode::Tag(a)]) = li.children.as_deref() else {
+            panic!("expected a single child tag node");
+        };
+        assert_eq!(a.tag, "a");
+        assert!(a.is_block_expansion);
+
+        let Some([HsmlNode::Tag(span)]) = a.children.as_deref() else {
+            panic!("expected a single child tag node");
+        };
+        assert_eq!(span.tag, "span");
+        assert!(!span.is_block_expansion);
+        assert_eq!(span.text.as_ref().unwrap().text, "Home");
+        assert!(
+            matches!(span.children.as_deref(), Some([HsmlNode::Text(text)]) if text.text == "nested"),
+            "nested lines belong to the innermost tag"
+        );
+    }
+
+    #[test]
+    fn it_should_return_script_tag_node_with_raw_text_block() {
+        let input =
+            "div\n  script.\n      indented();\n    if (a) {\n\n      b(\"#[i]\");\n    }\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (_, tag) = tag_node(input, context).unwrap();
+
+        let Some([HsmlNode::Tag(script)]) = tag.children.as_deref() else {
+            panic!("expected a single child tag node");
+        };
+
+        let text_node = script.text.as_ref().unwrap();
+        assert_eq!(
+            text_node.text,
+            "  indented();\nif (a) {\n\n  b(\"#[i]\");\n}"
+        );
+        assert_eq!(text_node.segments, None);
+    }
+
+    #[test]
+    fn it_should_return_tag_node_at_end_of_input() {
+        let input = "p Hello #[b world]";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (rest, tag) = tag_node(input, context).unwrap();
+
+        assert_eq!(tag.text.unwrap().text, "Hello #[b world]");
+        assert_eq!(rest, "");
+
+        let input = "p.";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let (rest, tag) = tag_node(input, context).unwrap();
+
+        assert_eq!(tag.classes, None);
+        assert_eq!(tag.text.unwrap().text, "");
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_not_return_unclosed_interpolated_tag_node() {
+        let input = "p Hello #[strong world\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let error = tag_node(input, context).unwrap_err();
+
+        assert_eq!(
+            error,
+            nom::Err::Failure(HsmlError::new(
+                &input[8..],
+                HsmlErrorKind::UnclosedInterpolation
+            ))
+        );
+    }
+
+    #[test]
+    fn it_should_not_return_script_tag_node_with_end_tag_in_text() {
+        let input = "script.\n  document.write(\"</SCRIPT>\");\n";
+        let context = &mut HsmlProcessContext::new(input);
+
+        let error = tag_node(input, context).unwrap_err();
+
+        assert_eq!(
+            error,
+            nom::Err::Failure(HsmlError::new(
+                &input[26..],
+                HsmlErrorKind::RawTextEndTag(String::from("script"))
+            ))
+        );
+    }
 }
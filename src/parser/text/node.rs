@@ -1,42 +1,299 @@
-use nom::IResult;
+use std::ops::Range;
 
-use crate::parser::HsmlProcessContext;
+use serde::Serialize;
 
-use super::process::{process_text, process_text_block};
+use crate::parser::{
+    HsmlNode, HsmlProcessContext,
+    error::{HsmlError, HsmlErrorKind, HsmlResult},
+    span::Span,
+    tag::node::interpolated_tag_node,
+};
 
-#[derive(Debug, PartialEq, Eq)]
+use super::process::{
+    decode_text, find_interpolation, process_interpolation_text, process_pipe_text, process_text,
+    process_text_block,
+};
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "text", rename_all = "camelCase")]
 pub struct TextNode {
+    /// The text with decoded escape sequences, interpolations stay as written
     pub text: String,
+    /// The text as written, only the indentation of text blocks is removed
+    pub raw_text: String,
+    /// Written as `!=`, the text is emitted as raw HTML
+    pub is_unescaped: bool,
+    /// Text and interpolated tag nodes in order, only set if the text contains an interpolation
+    pub segments: Option<Vec<HsmlNode>>,
+    pub span: Span,
+}
+
+fn literal_segment(
+    text_input: &str,
+    range: Range<usize>,
+    indent: Option<&str>,
+    context: &HsmlProcessContext,
+) -> HsmlNode {
+    let mut raw_text = text_input[range.clone()].to_string();
+
+    if let Some(indent) = indent {
+        if range.start == 0 {
+            raw_text = raw_text.trim_start_matches(indent).to_string();
+        }
+
+        raw_text = raw_text
+            .replace("\r\n", "\n")
+            .replace(&format!("\n{indent}"), "\n");
+    }
+
+    HsmlNode::Text(TextNode {
+        text: decode_text(&raw_text),
+        raw_text,
+        is_unescaped: false,
+        segments: None,
+        span: context.span_between(&text_input[range.start..], &text_input[range.end..]),
+    })
+}
+
+/// Split the first `text_len` bytes of `text_input` at every `#[...]` interpolation.
+///
+/// `indent` is stripped from the start of every line, like it is for text blocks.
+fn text_segments<'a>(
+    text_input: &'a str,
+    text_len: usize,
+    indent: Option<&str>,
+    context: &mut HsmlProcessContext,
+) -> Result<Option<Vec<HsmlNode>>, nom::Err<HsmlError<'a>>> {
+    let text = &text_input[..text_len];
+
+    if find_interpolation(text).is_none() {
+        return Ok(None);
+    }
+
+    let mut segments = vec![];
+    let mut literal_start = 0;
+
+    while let Some(index) = find_interpolation(&text[literal_start..]) {
+        let tag_start = literal_start + index;
+
+        let (rest, node) = interpolated_tag_node(&text_input[tag_start..], context)?;
+        let tag_end = text_input.len() - rest.len();
+
+        if literal_start < tag_start {
+            segments.push(literal_segment(
+                text_input,
+                literal_start..tag_start,
+                indent,
+                context,
+            ));
+        }
+
+        segments.push(HsmlNode::Tag(node));
+
+        literal_start = tag_end;
+    }
+
+    if literal_start < text_len {
+        segments.push(literal_segment(
+            text_input,
+            literal_start..text_len,
+            indent,
+            context,
+        ));
+    }
+
+    Ok(Some(segments))
 }
 
 pub fn text_block_node<'a>(
     input: &'a str,
     context: &mut HsmlProcessContext,
-) -> IResult<&'a str, TextNode> {
-    let (input, text) = process_text_block(input, context)?;
+) -> HsmlResult<'a, TextNode> {
+    let (rest, text) = process_text_block(input, context).map_err(nom::Err::convert)?;
+
+    let end = context.offset(rest);
+    let span = context.span(end - text.len(), end);
 
+    // same fallback as process_text_block for text blocks before the first indented line
     let indent_string = context
         .indent_string
-        .as_ref()
-        .unwrap()
+        .as_deref()
+        .unwrap_or("  ")
         .repeat(context.indent_level + 1);
 
+    let segments = text_segments(
+        &input[input.len() - rest.len() - text.len()..],
+        text.len(),
+        Some(&indent_string),
+        context,
+    )?;
+
     let newline_indent_replacement: &str = &format!("\n{}", &indent_string);
 
-    let text = text
+    let raw_text = text
         .trim_start_matches(&indent_string)
+        .replace("\r\n", "\n")
         .replace(newline_indent_replacement, "\n");
 
-    Ok((input, TextNode { text }))
+    Ok((
+        rest,
+        TextNode {
+            text: decode_text(&raw_text),
+            raw_text,
+            is_unescaped: false,
+            segments,
+            span,
+        },
+    ))
+}
+
+pub fn text_node<'a>(input: &'a str, context: &mut HsmlProcessContext) -> HsmlResult<'a, TextNode> {
+    let (rest, text) = process_text(input).map_err(nom::Err::convert)?;
+
+    let end = context.offset(rest);
+
+    let segments = text_segments(&input[1..], text.len(), None, context)?;
+
+    Ok((
+        rest,
+        TextNode {
+            text: decode_text(text),
+            raw_text: text.to_string(),
+            is_unescaped: false,
+            segments,
+            span: context.span(end - text.len(), end),
+        },
+    ))
 }
 
-pub fn text_node(input: &str) -> IResult<&str, TextNode> {
-    let (input, text) = process_text(input)?;
+/// A `| text` line, which is a standalone text node between the children of a tag.
+pub fn text_pipe_node<'a>(
+    input: &'a str,
+    context: &mut HsmlProcessContext,
+) -> HsmlResult<'a, TextNode> {
+    let (rest, text) = process_pipe_text(input).map_err(nom::Err::convert)?;
+
+    let segments = text_segments(
+        &input[input.len() - rest.len() - text.len()..],
+        text.len(),
+        None,
+        context,
+    )?;
 
     Ok((
-        input,
+        rest,
+        TextNode {
+            text: decode_text(text),
+            raw_text: text.to_string(),
+            is_unescaped: false,
+            segments,
+            span: context.span_between(input, rest),
+        },
+    ))
+}
+
+/// Fail at the first `</tag` inside the raw text of `tag`, the browser would end the element there.
+fn check_raw_text<'a>(
+    text_input: &'a str,
+    text_len: usize,
+    tag: &str,
+) -> Result<(), nom::Err<HsmlError<'a>>> {
+    let end_tag = format!("</{tag}");
+
+    let index = text_input[..text_len]
+        .to_ascii_lowercase()
+        .find(&end_tag.to_ascii_lowercase());
+
+    match index {
+        Some(index) => Err(nom::Err::Failure(HsmlError::new(
+            &text_input[index..],
+            HsmlErrorKind::RawTextEndTag(tag.to_string()),
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The text of a raw text element like `script`, which is not interpolated.
+pub fn raw_text_node<'a>(
+    input: &'a str,
+    tag: &str,
+    context: &HsmlProcessContext,
+) -> HsmlResult<'a, TextNode> {
+    let (rest, text) = process_text(input).map_err(nom::Err::convert)?;
+
+    check_raw_text(&input[1..], text.len(), tag)?;
+
+    Ok((
+        rest,
         TextNode {
             text: text.to_string(),
+            raw_text: text.to_string(),
+            is_unescaped: false,
+            segments: None,
+            span: context.span_between(&input[1..], rest),
+        },
+    ))
+}
+
+/// The text block of a raw text element like `script`.
+///
+/// Only the indentation of the block is removed, so deeper indented lines keep their relative indentation.
+pub fn raw_text_block_node<'a>(
+    input: &'a str,
+    tag: &str,
+    context: &mut HsmlProcessContext,
+) -> HsmlResult<'a, TextNode> {
+    let (rest, text) = process_text_block(input, context).map_err(nom::Err::convert)?;
+
+    let text_input = &input[input.len() - rest.len() - text.len()..];
+    check_raw_text(text_input, text.len(), tag)?;
+
+    let indent_string = context
+        .indent_string
+        .as_deref()
+        .unwrap_or("  ")
+        .repeat(context.indent_level + 1);
+
+    let text = text
+        .split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            line.strip_prefix(&indent_string)
+                .unwrap_or_else(|| line.trim_start())
+        })
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    Ok((
+        rest,
+        TextNode {
+            raw_text: text.clone(),
+            text,
+            is_unescaped: false,
+            segments: None,
+            span: context.span_between(text_input, rest),
+        },
+    ))
+}
+
+/// The text of an interpolated tag, which ends before the closing `]`.
+pub fn interpolated_text_node<'a>(
+    input: &'a str,
+    context: &mut HsmlProcessContext,
+) -> HsmlResult<'a, TextNode> {
+    let (rest, text) = process_interpolation_text(input).map_err(nom::Err::convert)?;
+
+    let segments = text_segments(&input[1..], text.len(), None, context)?;
+
+    Ok((
+        rest,
+        TextNode {
+            text: decode_text(text),
+            raw_text: text.to_string(),
+            is_unescaped: false,
+            segments,
+            span: context.span_between(&input[1..], rest),
         },
     ))
 }
@@ -45,25 +302,26 @@ pub fn text_node(input: &str) -> IResult<&str, TextNode> {
 mod tests {
     use crate::parser::{
         HsmlProcessContext,
-        text::node::{TextNode, text_block_node},
+        span::{Position, Span},
+        text::node::{TextNode, text_block_node, text_node, text_pipe_node},
     };
 
     #[test]
     fn it_should_return_text_block_node() {
+        let input = r#".
+        "Tailwind CSS is the only framework that I've seen scale
+        on large teams. It's easy to customize, adapts to any design,
+        and the build size is tiny."
+    figcaption.font-medium"#;
+
         let context = &mut HsmlProcessContext {
+            source: input,
             indent_string: Some(String::from("  ")),
             indent_level: 3,
+            ..Default::default()
         };
 
-        let (input, text_block) = text_block_node(
-            r#".
-        "Tailwind CSS is the only framework that I've seen scale
-        on large teams. It's easy to customize, adapts to any design,
-        and the build size is tiny."
-    figcaption.font-medium"#,
-            context,
-        )
-        .unwrap();
+        let (input, text_block) = text_block_node(input, context).unwrap();
 
         assert_eq!(
             text_block,
@@ -73,9 +331,59 @@ mod tests {
 on large teams. It's easy to customize, adapts to any design,
 and the build size is tiny.""#
                 ),
+                raw_text: String::from(
+                    r#""Tailwind CSS is the only framework that I've seen scale
+on large teams. It's easy to customize, adapts to any design,
+and the build size is tiny.""#
+                ),
+                is_unescaped: false,
+                segments: None,
+                span: Span::new(Position::new(2, 2, 1), Position::new(173, 4, 37)),
             }
         );
 
         assert_eq!(input, "\n    figcaption.font-medium");
     }
+
+    #[test]
+    fn it_should_return_text_node() {
+        let input = " hello world\n";
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, text) = text_node(input, &mut context).unwrap();
+
+        assert_eq!(
+            text,
+            TextNode {
+                text: String::from("hello world"),
+                raw_text: String::from("hello world"),
+                is_unescaped: false,
+                segments: None,
+                span: Span::new(Position::new(1, 1, 2), Position::new(12, 1, 13)),
+            }
+        );
+
+        assert_eq!(input, "\n");
+    }
+
+    #[test]
+    fn it_should_return_text_pipe_node() {
+        let input = "| Hello <world>\n";
+        let mut context = HsmlProcessContext::new(input);
+
+        let (input, text) = text_pipe_node(input, &mut context).unwrap();
+
+        assert_eq!(
+            text,
+            TextNode {
+                text: String::from("Hello <world>"),
+                raw_text: String::from("Hello <world>"),
+                is_unescaped: false,
+                segments: None,
+                span: Span::new(Position::new(0, 1, 1), Position::new(15, 1, 16)),
+            }
+        );
+
+        assert_eq!(input, "\n");
+    }
 }
@@ -1,7 +1,9 @@
 use nom::{
     IResult, Parser,
     branch::alt,
-    bytes::complete::{tag, take_until1},
+    bytes::complete::{tag, take_till, take_till1},
+    combinator::{eof, opt},
+    error::{Error, ErrorKind},
 };
 
 use crate::parser::HsmlProcessContext;
@@ -12,8 +14,8 @@ pub fn process_text_block<'a>(
 ) -> IResult<&'a str, &'a str> {
     let (rest, _) = tag(".")(input)?;
 
-    // eat one \r\n or \n
-    let (rest, _) = alt((tag("\r\n"), tag("\n"))).parse(rest)?;
+    // eat one \r\n or \n, the end of input ends an empty text block
+    let (rest, _) = alt((tag("\r\n"), tag("\n"), eof)).parse(rest)?;
 
     let indent_string: &str = if let Some(indent_string) = &context.indent_string {
         indent_string
@@ -23,47 +25,142 @@ pub fn process_text_block<'a>(
 
     let indent_string: &str = &indent_string.repeat(context.indent_level + 1);
 
-    let mut text_block_index = 0;
+    let mut text_block_end = 0;
+    let mut line_start = 0;
 
     // loop over each line until we find a line that does not fulfill the indentation
-    for (index, c) in rest.chars().enumerate() {
-        if c == '\n' {
-            // if next char is also a \n, then continue
-            let next_char = rest.chars().nth(index + 1);
-            if next_char == Some('\n') {
-                text_block_index = index + 1;
-                continue;
-            }
-
-            let line = &rest[index + 1..];
+    // blank lines in between belong to the text block
+    for line in rest.split_inclusive('\n') {
+        let line_content = line.trim_end_matches(['\r', '\n']);
 
-            // otherwise check the indentation and if it does not fulfill the indentation, then break
-            if !line.starts_with(indent_string) {
+        if !line_content.trim().is_empty() {
+            if !line_content.starts_with(indent_string) {
                 break;
             }
-        } else {
-            text_block_index = index;
-            continue;
+
+            text_block_end = line_start + line_content.len();
         }
+
+        line_start += line.len();
+    }
+
+    if text_block_end == 0 {
+        // an empty text block, the line ending stays for the next node
+        return Ok((&input[1..], ""));
     }
 
-    let text_block = &rest[..text_block_index + 1];
+    let text_block = &rest[..text_block_end];
 
-    let rest = &rest[text_block_index + 1..];
+    let rest = &rest[text_block_end..];
 
     Ok((rest, text_block))
 }
 
 pub fn process_text(input: &str) -> IResult<&str, &str> {
     let (input, _) = tag(" ")(input)?;
-    take_until1("\n")(input)
+
+    // read until end of line or end of input
+    let (rest, text) = take_till1(|c: char| c == '\n')(input)?;
+
+    // the `\r` of a `\r\n` line ending is not part of the text
+    match text.strip_suffix('\r') {
+        Some("") => Err(nom::Err::Error(Error::new(input, ErrorKind::TakeTill1))),
+        Some(text) => Ok((&input[text.len()..], text)),
+        None => Ok((rest, text)),
+    }
+}
+
+/// Process a `| text` line, the single space after the pipe is not part of the text.
+pub fn process_pipe_text(input: &str) -> IResult<&str, &str> {
+    let (input, _) = tag("|")(input)?;
+    let (input, _) = opt(tag(" ")).parse(input)?;
+
+    // read until end of line
+    take_till(|c: char| c == '\r' || c == '\n')(input)
+}
+
+/// Find the next `#[` that starts an interpolation, `\#[` is an escaped one.
+pub fn find_interpolation(text: &str) -> Option<usize> {
+    let mut is_escaped = false;
+
+    for (index, c) in text.char_indices() {
+        if is_escaped {
+            is_escaped = false;
+            continue;
+        }
+
+        if c == '\\' && (text[index + 1..].starts_with('\\') || text[index + 1..].starts_with("#["))
+        {
+            is_escaped = true;
+            continue;
+        }
+
+        if text[index..].starts_with("#[") {
+            return Some(index);
+        }
+    }
+
+    None
+}
+
+/// Decode the escape sequences of text, `\#[` becomes `#[` and `\\` becomes `\`.
+///
+/// Every other backslash is kept as is.
+pub fn decode_text(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut remaining = text;
+
+    while let Some(index) = remaining.find('\\') {
+        decoded.push_str(&remaining[..index]);
+
+        let escaped = &remaining[index + 1..];
+
+        if escaped.starts_with('\\') || escaped.starts_with("#[") {
+            decoded.push_str(&escaped[..1]);
+            remaining = &escaped[1..];
+        } else {
+            decoded.push('\\');
+            remaining = escaped;
+        }
+    }
+
+    decoded.push_str(remaining);
+
+    decoded
+}
+
+/// Process the text of an interpolated tag up to its closing `]`, which is not consumed.
+///
+/// Nested interpolations are part of the text, the interpolation has to be closed on the same line.
+pub fn process_interpolation_text(input: &str) -> IResult<&str, &str> {
+    let (input, _) = tag(" ")(input)?;
+
+    let mut depth = 0;
+
+    for (index, c) in input.char_indices() {
+        match c {
+            '[' if input[..index].ends_with('#') => depth += 1,
+            ']' if depth == 0 => return Ok((&input[index..], &input[..index])),
+            ']' => depth -= 1,
+            '\r' | '\n' => break,
+            _ => {}
+        }
+    }
+
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::TakeUntil,
+    )))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::parser::{
         HsmlProcessContext,
-        text::process::{process_text, process_text_block},
+        text::process::{
+            decode_text, find_interpolation, process_interpolation_text, process_pipe_text,
+            process_text, process_text_block,
+        },
     };
 
     #[test]
@@ -71,6 +168,7 @@ mod tests {
         let mut context = HsmlProcessContext {
             indent_string: Some(String::from("  ")),
             indent_level: 1,
+            ..Default::default()
         };
 
         let input = r#".
@@ -98,6 +196,22 @@ span other text
         );
     }
 
+    #[test]
+    fn it_should_process_text_block_with_multibyte_characters() {
+        let mut context = HsmlProcessContext {
+            indent_string: Some(String::from("  ")),
+            indent_level: 1,
+            ..Default::default()
+        };
+
+        let input = ".\n    Grüße 👋\n\n    日本語のテキスト\nspan 🤷\n";
+
+        let (rest, text_block) = process_text_block(input, &mut context).unwrap();
+
+        assert_eq!(text_block, "    Grüße 👋\n\n    日本語のテキスト");
+        assert_eq!(rest, "\nspan 🤷\n");
+    }
+
     #[test]
     fn test_process_text() {
         let input = " hello world\n";
@@ -107,4 +221,103 @@ span other text
         assert_eq!(text, "hello world");
         assert_eq!(rest, "\n");
     }
+
+    #[test]
+    fn it_should_process_text_with_crlf() {
+        let input = " hello world\r\n";
+
+        let (rest, text) = process_text(input).unwrap();
+
+        assert_eq!(text, "hello world");
+        assert_eq!(rest, "\r\n");
+    }
+
+    #[test]
+    fn it_should_process_text_at_end_of_input() {
+        let input = " hello world";
+
+        let (rest, text) = process_text(input).unwrap();
+
+        assert_eq!(text, "hello world");
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_process_text_block_at_end_of_input() {
+        let mut context = HsmlProcessContext::default();
+
+        let (rest, text_block) = process_text_block(".\n  last line", &mut context).unwrap();
+
+        assert_eq!(text_block, "  last line");
+        assert_eq!(rest, "");
+
+        let (rest, text_block) = process_text_block(".", &mut context).unwrap();
+
+        assert_eq!(text_block, "");
+        assert_eq!(rest, "");
+    }
+
+    #[test]
+    fn it_should_process_pipe_text() {
+        let input = "|  and more\n";
+
+        let (rest, text) = process_pipe_text(input).unwrap();
+
+        assert_eq!(text, " and more");
+        assert_eq!(rest, "\n");
+    }
+
+    #[test]
+    fn it_should_process_empty_pipe_text() {
+        let input = "|\r\n";
+
+        let (rest, text) = process_pipe_text(input).unwrap();
+
+        assert_eq!(text, "");
+        assert_eq!(rest, "\r\n");
+    }
+
+    #[test]
+    fn it_should_process_interpolation_text() {
+        let input = " see #[a(href=\"/\") #[b docs]]] and more\n";
+
+        let (rest, text) = process_interpolation_text(input).unwrap();
+
+        assert_eq!(text, "see #[a(href=\"/\") #[b docs]]");
+        assert_eq!(rest, "] and more\n");
+    }
+
+    #[test]
+    fn it_should_process_interpolation_text_with_multibyte_characters() {
+        let input = " ü #[em 😀] 日本]語\n";
+
+        let (rest, text) = process_interpolation_text(input).unwrap();
+
+        assert_eq!(text, "ü #[em 😀] 日本");
+        assert_eq!(rest, "]語\n");
+    }
+
+    #[test]
+    fn it_should_find_interpolation() {
+        assert_eq!(find_interpolation("a #[b] c"), Some(2));
+        assert_eq!(find_interpolation("a \\#[b] #[c]"), Some(8));
+        assert_eq!(find_interpolation("a \\\\#[b]"), Some(4));
+        assert_eq!(find_interpolation("a # [b]"), None);
+        assert_eq!(find_interpolation("ü \\#[b] 😀 #[c]"), Some(14));
+    }
+
+    #[test]
+    fn it_should_decode_text() {
+        assert_eq!(decode_text(r"\#[b] \\ \n \#"), r"#[b] \ \n \#");
+        assert_eq!(decode_text("no escapes"), "no escapes");
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_process_unclosed_interpolation_text() {
+        let input = " bold\n]";
+
+        assert!(process_interpolation_text(input).is_err());
+    }
 }
@@ -0,0 +1,431 @@
+use serde::Serialize;
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    compiler::{HsmlCompileOptions, compile, error::HsmlCompileError},
+    parser::{
+        self, RootNode,
+        error::HsmlParseError,
+        span::{self, Position},
+    },
+};
+
+/// TypeScript definitions of the object tree returned by [`parse`].
+pub const AST_TYPES: &str = r#"
+export interface Position {
+  /** Offset into the source in UTF-16 code units, like the index of a JavaScript string */
+  offset: number;
+  line: number;
+  /** Counted in UTF-16 code units */
+  column: number;
+}
+
+export interface Span {
+  start: Position;
+  end: Position;
+}
+
+export interface RootNode {
+  type: "root";
+  nodes: HsmlNode[];
+}
+
+export type HsmlNode = DoctypeNode | TagNode | CommentNode | TextNode | RawHtmlNode;
+
+export interface DoctypeNode {
+  type: "doctype";
+  value: string;
+  span: Span;
+}
+
+export interface TagNode {
+  type: "tag";
+  tag: string;
+  id: IdNode | null;
+  classes: ClassNode[] | null;
+  attributes: (AttributeNode | CommentNode)[] | null;
+  text: TextNode | null;
+  children: HsmlNode[] | null;
+  isBlockExpansion: boolean;
+  span: Span;
+}
+
+export interface IdNode {
+  type: "id";
+  id: string;
+  span: Span;
+}
+
+export interface ClassNode {
+  type: "class";
+  name: string;
+  span: Span;
+}
+
+export type AttributeQuote = "double" | "single" | "backtick" | "unquoted";
+
+export interface AttributeNode {
+  type: "attribute";
+  key: string;
+  value: string | null;
+  rawValue: string | null;
+  quote: AttributeQuote | null;
+  isUnescaped: boolean;
+  span: Span;
+}
+
+export interface CommentNode {
+  type: "comment";
+  text: string;
+  isDev: boolean;
+  span: Span;
+}
+
+export interface TextNode {
+  type: "text";
+  text: string;
+  rawText: string;
+  isUnescaped: boolean;
+  segments: (TextNode | TagNode)[] | null;
+  span: Span;
+}
+
+export interface RawHtmlNode {
+  type: "rawHtml";
+  html: string;
+  span: Span;
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_AST_TYPES: &str = AST_TYPES;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "RootNode")]
+    pub type JsRootNode;
+}
+
+/// The error handed to JavaScript, thrown as an `Error` with additional `line`, `column` and `code` properties.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HsmlJsError {
+    pub message: String,
+    /// `None` if the error is not related to a position in the source, e.g. for invalid options
+    pub line: Option<usize>,
+    /// Counted in UTF-16 code units, like the index of a JavaScript string
+    pub column: Option<usize>,
+    pub code: &'static str,
+}
+
+impl HsmlJsError {
+    fn invalid_options(message: String) -> Self {
+        Self {
+            message,
+            line: None,
+            column: None,
+            code: "invalid-options",
+        }
+    }
+
+    fn at(source: &str, position: Position, message: String, code: &'static str) -> Self {
+        let position = Utf16Positions::new(source).position(position.offset, position.line);
+
+        Self {
+            message,
+            line: Some(position.line),
+            column: Some(position.column),
+            code,
+        }
+    }
+
+    fn from_parse_error(source: &str, error: HsmlParseError) -> Self {
+        Self::at(source, error.position(), error.message(), error.code())
+    }
+
+    fn from_compile_error(source: &str, error: HsmlCompileError) -> Self {
+        Self::at(source, error.position(), error.message(), error.code())
+    }
+}
+
+impl From<HsmlJsError> for JsValue {
+    fn from(error: HsmlJsError) -> Self {
+        let js_error = js_sys::Error::new(&error.message);
+
+        // setting properties on a fresh object can not fail
+        let _ = js_sys::Reflect::set(&js_error, &"code".into(), &error.code.into());
+
+        if let (Some(line), Some(column)) = (error.line, error.column) {
+            let _ = js_sys::Reflect::set(&js_error, &"line".into(), &line.into());
+            let _ = js_sys::Reflect::set(&js_error, &"column".into(), &column.into());
+        }
+
+        js_error.into()
+    }
+}
+
+/// Converts byte offsets into UTF-16 code units, which JavaScript uses to index strings.
+struct Utf16Positions {
+    /// The UTF-16 offset of every byte offset of the source
+    offsets: Vec<usize>,
+    line_starts: Vec<usize>,
+}
+
+impl Utf16Positions {
+    fn new(source: &str) -> Self {
+        let mut offsets = Vec::with_capacity(source.len() + 1);
+        let mut utf16_offset = 0;
+
+        for c in source.chars() {
+            offsets.extend(std::iter::repeat_n(utf16_offset, c.len_utf8()));
+            utf16_offset += c.len_utf16();
+        }
+
+        offsets.push(utf16_offset);
+
+        Self {
+            offsets,
+            line_starts: span::line_starts(source),
+        }
+    }
+
+    /// The position of the byte `offset` on the one-based `line`, with offset and column in UTF-16 code units.
+    fn position(&self, offset: usize, line: usize) -> Position {
+        let line_start = self.line_starts[line - 1];
+        let offset = self.offsets[offset];
+
+        Position::new(offset, line, offset - self.offsets[line_start] + 1)
+    }
+}
+
+/// Convert the `start` and `end` of every `span` in the serialized tree into UTF-16 positions.
+fn convert_spans(value: &mut Value, positions: &Utf16Positions) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                if key != "span" {
+                    convert_spans(value, positions);
+                    continue;
+                }
+
+                for key in ["start", "end"] {
+                    let position = &mut value[key];
+
+                    if let (Some(offset), Some(line)) =
+                        (position["offset"].as_u64(), position["line"].as_u64())
+                    {
+                        let utf16_position = positions.position(offset as usize, line as usize);
+
+                        position["offset"] = utf16_position.offset.into();
+                        position["column"] = utf16_position.column.into();
+                    }
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                convert_spans(value, positions);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The object tree handed to JavaScript, with every span in UTF-16 code units.
+fn js_tree(source: &str, root_node: &RootNode) -> serde_json::Result<Value> {
+    let mut value = serde_json::to_value(root_node)?;
+
+    convert_spans(&mut value, &Utf16Positions::new(source));
+
+    Ok(value)
+}
+
+fn compile_source(source: &str, options: &HsmlCompileOptions) -> Result<String, HsmlJsError> {
+    let ast =
+        parser::parse::parse(source).map_err(|err| HsmlJsError::from_parse_error(source, err))?;
+
+    compile(&ast, options).map_err(|err| HsmlJsError::from_compile_error(source, err))
+}
+
+/// Compile hsml source to html.
+///
+/// `options` is an optional object with the camelCase fields of [`HsmlCompileOptions`].
+#[wasm_bindgen]
+pub fn compile_content(source: &str, options: JsValue) -> Result<String, JsValue> {
+    let options = serde_wasm_bindgen::from_value::<Option<HsmlCompileOptions>>(options)
+        .map_err(|err| HsmlJsError::invalid_options(err.to_string()))?
+        .unwrap_or_default();
+
+    Ok(compile_source(source, &options)?)
+}
+
+/// Parse hsml source into a plain object tree, described by the `RootNode` type definition.
+#[wasm_bindgen]
+pub fn parse(source: &str) -> Result<JsRootNode, JsValue> {
+    let root_node =
+        parser::parse::parse(source).map_err(|err| HsmlJsError::from_parse_error(source, err))?;
+
+    let value = js_tree(source, &root_node)
+        .map_err(|err| JsValue::from(js_sys::Error::new(&err.to_string())))?;
+
+    // `null` instead of `undefined` for missing values, like in the JSON output
+    let value = value.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+    Ok(value.unchecked_into())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use crate::{
+        compiler::{HsmlCompileOptions, HsmlIndentStyle, HsmlOutputMode},
+        parser::parse::parse,
+        wasm::{AST_TYPES, HsmlJsError, compile_source, js_tree},
+    };
+
+    /// Check that every key of every serialized node is declared in the interface of its type.
+    fn assert_declared(value: &Value) {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(node_type)) = object.get("type") {
+                    let declaration_start = AST_TYPES
+                        .find(&format!("\n  type: \"{node_type}\";"))
+                        .unwrap_or_else(|| panic!("missing interface for `{node_type}`"));
+                    let declaration = &AST_TYPES[declaration_start..];
+                    let declaration = &declaration[..declaration.find('}').unwrap()];
+
+                    for key in object.keys() {
+                        assert!(
+                            declaration.contains(&format!("\n  {key}: ")),
+                            "missing `{key}` in interface for `{node_type}`"
+                        );
+                    }
+                }
+
+                object.values().for_each(assert_declared);
+            }
+            Value::Array(values) => values.iter().for_each(assert_declared),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn it_should_compile_source() {
+        assert_eq!(
+            compile_source("p Hello\n", &HsmlCompileOptions::default()),
+            Ok(String::from("<p>Hello</p>"))
+        );
+    }
+
+    #[test]
+    fn it_should_deserialize_compile_options() {
+        let options: HsmlCompileOptions = serde_json::from_str(
+            r#"{ "mode": "xhtml", "escapeText": false, "pretty": { "indentStyle": "tabs" } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(options.mode, HsmlOutputMode::Xhtml);
+        assert!(!options.escape_text);
+        assert!(options.escape_attribute_values);
+        assert!(options.mode_from_doctype);
+        assert_eq!(
+            options
+                .pretty
+                .map(|pretty| (pretty.indent_style, pretty.indent_width)),
+            Some((HsmlIndentStyle::Tabs, 2))
+        );
+    }
+
+    #[test]
+    fn it_should_declare_types_of_every_node() {
+        let root_node = parse(concat!(
+            "doctype html\n",
+            "//! native comment\n",
+            "<!-- raw html -->\n",
+            "div#main.card(href=\"/\" hidden // dev comment\n)\n",
+            "  p Hello #[b world]\n",
+            "  | pipe text\n",
+        ))
+        .unwrap();
+
+        let value = serde_json::to_value(&root_node).unwrap();
+
+        assert_declared(&value);
+    }
+
+    #[test]
+    fn it_should_count_positions_in_utf16_code_units() {
+        let source = "p 😀 #[b x]\np 日本 ok\n";
+
+        let value = js_tree(source, &parse(source).unwrap()).unwrap();
+
+        let tag_span = &value["nodes"][0]["text"]["segments"][1]["span"];
+
+        assert_eq!(
+            tag_span["start"],
+            json!({ "offset": 5, "line": 1, "column": 6 })
+        );
+        assert_eq!(
+            tag_span["end"],
+            json!({ "offset": 11, "line": 1, "column": 12 })
+        );
+
+        let text_span = &value["nodes"][1]["text"]["span"];
+
+        assert_eq!(
+            text_span["start"],
+            json!({ "offset": 14, "line": 2, "column": 3 })
+        );
+        assert_eq!(
+            text_span["end"],
+            json!({ "offset": 19, "line": 2, "column": 8 })
+        );
+    }
+
+    // Negative tests
+
+    #[test]
+    fn it_should_not_compile_source_with_parse_error() {
+        assert_eq!(
+            compile_source("div#id1#id2\n", &HsmlCompileOptions::default()),
+            Err(HsmlJsError {
+                message: String::from("duplicate id `id2`, a tag can only have one id"),
+                line: Some(1),
+                column: Some(8),
+                code: "duplicate-id",
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_not_compile_source_with_compile_error() {
+        assert_eq!(
+            compile_source("img Text\n", &HsmlCompileOptions::default()),
+            Err(HsmlJsError {
+                message: String::from("void element `img` can not have content"),
+                line: Some(1),
+                column: Some(5),
+                code: "void-element-content",
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_count_error_column_in_utf16_code_units() {
+        assert_eq!(
+            compile_source("div#😀#b\n", &HsmlCompileOptions::default()),
+            Err(HsmlJsError {
+                message: String::from("duplicate id `b`, a tag can only have one id"),
+                line: Some(1),
+                column: Some(7),
+                code: "duplicate-id",
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_not_deserialize_unknown_compile_options() {
+        assert!(serde_json::from_str::<HsmlCompileOptions>(r#"{ "escapeHtml": false }"#).is_err());
+    }
+}